@@ -1,51 +1,47 @@
-use std::{fmt::Display, sync::Arc};
+use std::{
+    f32::consts::{FRAC_PI_2, PI, TAU},
+    fmt::Display,
+    sync::Arc,
+};
 
 use bevy::{
-    app::{Plugin, Update},
-    color::{palettes::tailwind::ORANGE_400, Color, Srgba},
+    app::{Plugin, Startup, Update},
+    asset::{AssetServer, Assets, Handle},
     ecs::{
         component::Component,
         entity::Entity,
-        query::Without,
-        system::{Commands, Query, Res},
+        query::{Added, With, Without},
+        system::{Commands, EntityCommands, Query, Res, ResMut, Resource},
     },
-    hierarchy::{ChildBuild, ChildBuilder},
-    math::{Vec2, Vec3},
-    sprite::Sprite,
+    hierarchy::{BuildChildren, ChildBuild, ChildBuilder, Children, DespawnRecursiveExt},
+    math::{Quat, UVec2, Vec2, Vec3},
+    sprite::{Sprite, TextureAtlas, TextureAtlasLayout},
+    text::{Text2d, TextFont},
     time::Time,
     transform::components::Transform,
+    utils::default,
 };
-use enum_map::Enum;
 use once_cell::sync::Lazy;
 use rand::{
     distr::{Distribution, StandardUniform},
     rngs::ThreadRng,
+    seq::IndexedRandom,
     Rng,
 };
+use serde::{Deserialize, Serialize};
 use strum::EnumCount;
 use strum_macros::{EnumCount, FromRepr};
 
 use crate::{
-    merge::fox_lot,
-    money::Cent,
+    fox::species::FoxSpecies,
+    merge::{fox_drag::FoxDrag, fox_lot},
     search::animation::{Direction, Height, Jump, Speed},
-    Money,
+    unix_seconds, Money,
 };
 
-#[derive(FromRepr, EnumCount, Debug, Default, Clone, Copy, Enum)]
-#[repr(u32)]
-pub(crate) enum FoxSpecies {
-    #[default]
-    Vulpes,
-    Corsac,
-}
-impl_enum_distribution!(FoxSpecies);
-impl Display for FoxSpecies {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{self:?}")
-    }
-}
-#[derive(Debug, Clone)]
+pub mod species;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub(crate) struct Name(Arc<str>);
 impl Display for Name {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -161,7 +157,7 @@ impl Distribution<Name> for StandardUniform {
         POSSIBLE_NAMES[rng.random_range(0..POSSIBLE_NAMES.len())].clone()
     }
 }
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub(crate) struct Age(u32);
 impl Age {
     const MAX_RANDOM_AGE: Self = Self(6);
@@ -181,40 +177,133 @@ impl Distribution<Age> for StandardUniform {
         Age(rng.random_range(0..Age::MAX_RANDOM_AGE.0))
     }
 }
-#[derive(Component, Debug, Clone)]
+/// How content a fox is, which scales its income. Foxes lose it by waiting too long for room in
+/// a sanctuary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Happiness(u32);
+impl Happiness {
+    const MAX: Self = Self(10);
+
+    const fn fraction(self) -> f64 {
+        self.0 as f64 / Self::MAX.0 as f64
+    }
+}
+impl Default for Happiness {
+    fn default() -> Self {
+        Self::MAX
+    }
+}
+impl Display for Happiness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.0, Self::MAX.0)
+    }
+}
+/// Tells foxes apart for as long as they're in the game, even if they share a name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub(crate) struct FoxId(u64);
+impl Display for FoxId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+/// Hands out every [`FoxId`] exactly once. The next id is saved with the game, so ids stay unique
+/// across sessions.
+#[derive(Resource, Debug, Default)]
+pub(crate) struct FoxIds {
+    next: u64,
+}
+impl FoxIds {
+    pub(crate) const fn new(next: u64) -> Self {
+        Self { next }
+    }
+    pub(crate) const fn next(&self) -> u64 {
+        self.next
+    }
+    pub(crate) const fn allocate(&mut self) -> FoxId {
+        let fox_id = FoxId(self.next);
+        self.next += 1;
+        fox_id
+    }
+}
+/// Foxes that are neither jumping, doing an [`ActivityAnimation`] nor being dragged.
+type IdleFoxFilter = (Without<Jump>, Without<ActivityAnimation>, Without<FoxDrag>);
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct Fox {
+    /// Foxes saved before ids came from [`FoxIds`] are given new ones when loaded.
+    #[serde(default)]
+    id: FoxId,
     species: FoxSpecies,
     name: Name,
     age: Age,
     favorite_activity: Activity,
     primary_problem: Problem,
     secondary_problem: Problem,
-    time_till_jump: f32,
+    /// Foxes saved before happiness existed are perfectly happy.
+    #[serde(default)]
+    happiness: Happiness,
 }
-static FOX_COLOR: Lazy<Color> = Lazy::new(|| Color::from(Fox::SRGBA));
 impl Fox {
-    const WIDTH: f32 = 10.;
-    const HEIGHT: f32 = Self::WIDTH / 2.;
-    const SRGBA: Srgba = ORANGE_400;
-    const JUMP_TIME_BOUNDS: Vec2 = Vec2 { x: 3., y: 6. };
+    pub(crate) const SIZE: f32 = 16.;
     const JUMP_DISTANCE: f32 = 15.;
+    /// How many income payouts releasing a fox is worth.
+    const RELEASE_REWARD_PAYOUTS: u64 = 100;
 
-    pub(crate) fn spawn(&self, fox_sanctuary: &mut ChildBuilder<'_>, translation: Vec3) {
-        fox_sanctuary.spawn((
-            self.clone(),
-            Transform::from_translation(translation),
-            Sprite::from_color(*FOX_COLOR, Vec2::new(Self::WIDTH, Self::HEIGHT)),
-        ));
+    pub(crate) fn spawn(self, fox_sanctuary: &mut ChildBuilder<'_>, translation: Vec3) {
+        fox_sanctuary.spawn((self, Transform::from_translation(translation)));
+    }
+    /// Gives newly spawned foxes their species' sprite sheet and a [`JumpTimer`].
+    #[allow(clippy::needless_pass_by_value)]
+    fn init(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        fox_sprite_sheet_layout: Res<FoxSpriteSheetLayout>,
+        foxes_q: Query<(Entity, &Self), Added<Self>>,
+    ) {
+        for (entity, fox) in &foxes_q {
+            let species_data = fox.species.data();
+            commands.entity(entity).insert((
+                Sprite {
+                    image: asset_server.load(&species_data.sprite_sheet),
+                    texture_atlas: Some(TextureAtlas {
+                        layout: fox_sprite_sheet_layout.0.clone(),
+                        index: FoxFrame::Idle as usize,
+                    }),
+                    custom_size: Some(Vec2::splat(Self::SIZE)),
+                    ..default()
+                },
+                JumpTimer::new(),
+            ));
+        }
+    }
+    /// Shows the [`FoxFrame`] for how far through its [`Jump`] each fox is, facing the way it's
+    /// jumping.
+    #[allow(clippy::needless_pass_by_value)]
+    fn animate_system(mut foxes_q: Query<(&mut Sprite, Option<&Jump>), With<Self>>) {
+        for (mut sprite, jump) in &mut foxes_q {
+            let fox_frame = jump.map_or(FoxFrame::Idle, |jump| FoxFrame::during(jump.progress()));
+            if let Some(texture_atlas) = &mut sprite.texture_atlas {
+                if texture_atlas.index != fox_frame as usize {
+                    texture_atlas.index = fox_frame as usize;
+                }
+            }
+            // Jumping up or down keeps the way the fox was already facing
+            match jump.map(Jump::direction) {
+                Some(Direction::Left) if !sprite.flip_x => sprite.flip_x = true,
+                Some(Direction::Right) if sprite.flip_x => sprite.flip_x = false,
+                _ => {}
+            }
+        }
     }
 
-    pub(crate) fn new_random(species: FoxSpecies) -> Self {
+    pub(crate) fn new_random(species: FoxSpecies, fox_ids: &mut FoxIds) -> Self {
         let mut rng: ThreadRng = rand::rng();
         let primary_problem = Problem::new(rng.random());
         Self {
+            id: fox_ids.allocate(),
             species,
             name: rng.random(),
             age: rng.random(),
-            favorite_activity: Activity::new(),
+            favorite_activity: Activity::new(species),
             primary_problem: primary_problem.clone(),
             secondary_problem: {
                 let mut secondary_problem = Problem::new(rng.random());
@@ -223,23 +312,34 @@ impl Fox {
                 }
                 secondary_problem
             },
-            time_till_jump: Self::get_random_jump_time(),
+            happiness: Happiness::default(),
         }
     }
-    fn get_random_jump_time() -> f32 {
-        rand::random_range(Self::JUMP_TIME_BOUNDS.x..Self::JUMP_TIME_BOUNDS.y)
+    /// Stops the fox mid jump or activity, leaving it upright wherever it is.
+    pub(crate) fn interrupt(fox_commands: &mut EntityCommands, transform: &mut Transform) {
+        fox_commands.remove::<(Jump, ActivityAnimation)>();
+        transform.rotation = Quat::IDENTITY;
+        transform.scale = Vec3::ONE;
     }
     #[allow(clippy::needless_pass_by_value)]
     fn jump_system(
         mut commands: Commands,
         time: Res<Time>,
-        mut foxes_q: Query<(Entity, &mut Self, &Transform), Without<Jump>>,
+        mut foxes_q: Query<(Entity, &Self, &mut JumpTimer, &Transform), IdleFoxFilter>,
     ) {
-        for (entity, mut fox, transform) in &mut foxes_q {
+        for (entity, fox, mut jump_timer, transform) in &mut foxes_q {
             let translation = transform.translation;
-            fox.time_till_jump -= time.delta_secs();
-            if fox.time_till_jump <= 0. {
-                fox.time_till_jump = Self::get_random_jump_time();
+            jump_timer.0 -= time.delta_secs();
+            if jump_timer.0 <= 0. {
+                *jump_timer = JumpTimer::new();
+                // Satisfied foxes spend some of their time on their favorite activity instead
+                if fox.favorite_activity.satisfied && rand::random_bool(0.5) {
+                    commands.entity(entity).insert(ActivityAnimation::new(
+                        fox.favorite_activity.activity_type,
+                        *transform,
+                    ));
+                    continue;
+                }
                 let allowed_directions = {
                     let mut allowed_direction = vec![];
                     if translation.x - Self::JUMP_DISTANCE >= fox_lot::MIN_FOX_POSITION.x {
@@ -273,6 +373,13 @@ impl Fox {
         }
     }
 
+    pub(crate) const fn id(&self) -> FoxId {
+        self.id
+    }
+    pub(crate) const fn reassign_id(&mut self, fox_ids: &mut FoxIds) {
+        self.id = fox_ids.allocate();
+    }
+
     pub(crate) const fn name(&self) -> &Name {
         &self.name
     }
@@ -284,40 +391,199 @@ impl Fox {
     pub(crate) const fn favorite_activity_type(&self) -> ActivityType {
         self.favorite_activity.activity_type
     }
+    pub(crate) const fn is_satisfied(&self) -> bool {
+        self.favorite_activity.satisfied
+    }
+    pub(crate) const fn set_satisfied(&mut self, satisfied: bool) {
+        self.favorite_activity.satisfied = satisfied;
+    }
 
     pub(crate) const fn primary_problem_type(&self) -> ProblemType {
         self.primary_problem.problem_type
     }
 
+    const fn problem(&self, problem_slot: ProblemSlot) -> &Problem {
+        match problem_slot {
+            ProblemSlot::Primary => &self.primary_problem,
+            ProblemSlot::Secondary => &self.secondary_problem,
+        }
+    }
+    fn problem_mut(&mut self, problem_slot: ProblemSlot) -> &mut Problem {
+        match problem_slot {
+            ProblemSlot::Primary => &mut self.primary_problem,
+            ProblemSlot::Secondary => &mut self.secondary_problem,
+        }
+    }
+    pub(crate) fn problem_state(&self, problem_slot: ProblemSlot, now: u64) -> ProblemState {
+        self.problem(problem_slot).state(now)
+    }
+    /// Reveals the primary problem, or the secondary one once the primary is known.
+    ///
+    /// Returns `false` if both were already known.
+    pub(crate) fn examine(&mut self) -> bool {
+        let problem = if self.primary_problem.known {
+            &mut self.secondary_problem
+        } else {
+            &mut self.primary_problem
+        };
+        let newly_known = !problem.known;
+        problem.known = true;
+        newly_known
+    }
+    /// Starts treating a known problem, which is fixed [`Treatment::seconds`] after `now`.
+    pub(crate) fn start_treatment(&mut self, problem_slot: ProblemSlot, now: u64) {
+        let problem = self.problem_mut(problem_slot);
+        if problem.state(now) == ProblemState::Untreated(problem.problem_type) {
+            problem.treatment_ready_at = Some(now + problem.problem_type.treatment().seconds);
+        }
+    }
+    /// Fixes every problem whose treatment is done by `now`, returning `true` if there were any.
+    pub(crate) fn finish_treatments(&mut self, now: u64) -> bool {
+        let mut finished = false;
+        for problem in [&mut self.primary_problem, &mut self.secondary_problem] {
+            if problem
+                .treatment_ready_at
+                .is_some_and(|treatment_ready_at| treatment_ready_at <= now)
+            {
+                problem.treatment_ready_at = None;
+                problem.fixed = true;
+                finished = true;
+            }
+        }
+        finished
+    }
+    /// How long until the soonest treatment in progress is done.
+    pub(crate) fn treatment_seconds_left(&self, now: u64) -> Option<u64> {
+        ProblemSlot::ALL
+            .into_iter()
+            .filter_map(|problem_slot| match self.problem_state(problem_slot, now) {
+                ProblemState::Treating { seconds_left, .. } => Some(seconds_left),
+                _ => None,
+            })
+            .min()
+    }
+
+    /// Whether both problems are fixed, so the fox can be released back to the wild.
+    pub(crate) const fn is_rehabilitated(&self) -> bool {
+        self.primary_problem.fixed && self.secondary_problem.fixed
+    }
+    /// What releasing the fox pays, once.
+    pub(crate) fn release_reward(&self) -> Money {
+        self.income().saturating_mul(Self::RELEASE_REWARD_PAYOUTS)
+    }
+
+    pub(crate) const fn happiness(&self) -> Happiness {
+        self.happiness
+    }
+    pub(crate) const fn lose_happiness(&mut self) {
+        self.happiness.0 = self.happiness.0.saturating_sub(1);
+    }
+
     pub(crate) const fn species(&self) -> FoxSpecies {
         self.species
     }
 
     pub(crate) fn income(&self) -> Money {
-        Money::from(Cent(
-            10 + if self.favorite_activity.satisfied {
+        let mut income = self.species.data().base_income.clone();
+        income += &Money::from_cents(
+            if self.favorite_activity.satisfied {
                 10
             } else {
                 0
             } + if self.primary_problem.fixed { 10 } else { 0 }
                 + if self.secondary_problem.fixed { 10 } else { 0 },
-        ))
+        );
+        income
+            .checked_mul_rate(self.happiness.fraction())
+            .unwrap_or(Money::ZERO)
     }
 }
-#[derive(Debug, Clone)]
+/// How long until a fox next jumps or plays. Kept apart from [`Fox`] since it changes every frame
+/// and isn't saved.
+#[derive(Component)]
+struct JumpTimer(f32);
+impl JumpTimer {
+    const BOUNDS: Vec2 = Vec2 { x: 3., y: 6. };
+
+    fn new() -> Self {
+        Self(rand::random_range(Self::BOUNDS.x..Self::BOUNDS.y))
+    }
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct Activity {
     activity_type: ActivityType,
     satisfied: bool,
 }
 impl Activity {
-    fn new() -> Self {
+    /// Picks one of the species' preferred activities, or any activity if it has none.
+    fn new(species: FoxSpecies) -> Self {
+        let mut rng = rand::rng();
         Self {
-            activity_type: rand::random(),
+            activity_type: species
+                .data()
+                .preferred_activities
+                .choose(&mut rng)
+                .copied()
+                .unwrap_or_else(|| rng.random()),
             satisfied: false,
         }
     }
 }
-#[derive(Debug, FromRepr, EnumCount, PartialEq, Eq, Clone, Copy)]
+/// A satisfied fox playing out its favorite [`ActivityType`] in place, after which it's put back
+/// how it was.
+#[derive(Component)]
+struct ActivityAnimation {
+    activity_type: ActivityType,
+    original_transform: Transform,
+    time_since_start: f32,
+}
+impl ActivityAnimation {
+    const TOTAL_TIME: f32 = 1.5;
+
+    const fn new(activity_type: ActivityType, original_transform: Transform) -> Self {
+        Self {
+            activity_type,
+            original_transform,
+            time_since_start: 0.,
+        }
+    }
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(
+        mut commands: Commands,
+        time: Res<Time>,
+        mut activity_animations_q: Query<(Entity, &mut Self, &mut Transform)>,
+    ) {
+        for (entity, mut activity_animation, mut transform) in &mut activity_animations_q {
+            activity_animation.time_since_start += time.delta_secs();
+            if activity_animation.time_since_start >= Self::TOTAL_TIME {
+                *transform = activity_animation.original_transform;
+                commands.entity(entity).remove::<Self>();
+                continue;
+            }
+            *transform = activity_animation.transform();
+        }
+    }
+    fn transform(&self) -> Transform {
+        let progress = self.time_since_start / Self::TOTAL_TIME;
+        // Rises from 0 to 1 and back over the whole animation
+        let swell = (progress * PI).sin();
+        let mut transform = self.original_transform;
+        match self.activity_type {
+            ActivityType::Pouncing => transform.translation.y += 8. * swell,
+            ActivityType::Digging => transform.translation.x += 1.5 * (progress * 10. * TAU).sin(),
+            ActivityType::Playing => transform.rotate_z(progress * TAU),
+            ActivityType::Hunting => {
+                transform.scale.y *= 1. - 0.4 * swell;
+                transform.translation.x += 3. * (progress * TAU).sin();
+            }
+            ActivityType::Tunneling => transform.scale *= 1. - 0.8 * swell,
+            ActivityType::Exploring => transform.rotate_z(0.4 * (progress * 2. * TAU).sin()),
+            ActivityType::Sunbathing => transform.rotate_z(FRAC_PI_2 * swell),
+        }
+        transform
+    }
+}
+#[derive(Debug, FromRepr, EnumCount, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 #[repr(u32)]
 pub(crate) enum ActivityType {
     Pouncing,
@@ -345,12 +611,16 @@ impl Display for ActivityType {
         )
     }
 }
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct Problem {
     #[allow(clippy::struct_field_names)]
     problem_type: ProblemType,
+    #[serde(default)]
     known: bool,
     fixed: bool,
+    /// When the treatment in progress is done, in [`unix_seconds`].
+    #[serde(default)]
+    treatment_ready_at: Option<u64>,
 }
 impl Problem {
     const fn new(problem_type: ProblemType) -> Self {
@@ -358,11 +628,67 @@ impl Problem {
             problem_type,
             known: false,
             fixed: false,
+            treatment_ready_at: None,
+        }
+    }
+    fn state(&self, now: u64) -> ProblemState {
+        if !self.known {
+            ProblemState::Unknown
+        } else if self.fixed {
+            ProblemState::Fixed(self.problem_type)
+        } else if let Some(treatment_ready_at) = self.treatment_ready_at {
+            ProblemState::Treating {
+                problem_type: self.problem_type,
+                seconds_left: treatment_ready_at.saturating_sub(now),
+            }
+        } else {
+            ProblemState::Untreated(self.problem_type)
+        }
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ProblemSlot {
+    Primary,
+    Secondary,
+}
+impl ProblemSlot {
+    pub(crate) const ALL: [Self; 2] = [Self::Primary, Self::Secondary];
+}
+impl Display for ProblemSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Primary => "Primary",
+                Self::Secondary => "Secondary",
+            }
+        )
+    }
+}
+/// What the player knows about a fox's problem and how far its treatment has got.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ProblemState {
+    Unknown,
+    Untreated(ProblemType),
+    Treating {
+        problem_type: ProblemType,
+        seconds_left: u64,
+    },
+    Fixed(ProblemType),
+}
+impl Display for ProblemState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unknown => write!(f, "Unknown"),
+            Self::Untreated(problem_type) => write!(f, "{problem_type}"),
+            Self::Treating { problem_type, .. } => write!(f, "{problem_type}, being treated"),
+            Self::Fixed(problem_type) => write!(f, "{problem_type}, fixed"),
         }
     }
 }
 impl_enum_distribution!(ActivityType);
-#[derive(Debug, FromRepr, EnumCount, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, FromRepr, EnumCount, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 #[repr(u32)]
 pub(crate) enum ProblemType {
     Malnourished,
@@ -388,10 +714,153 @@ impl Display for ProblemType {
         )
     }
 }
+impl ProblemType {
+    pub(crate) const fn treatment(self) -> Treatment {
+        match self {
+            Self::Malnourished => Treatment {
+                name: "Feeding Plan",
+                price: Money::new(10, 0),
+                seconds: 30,
+            },
+            Self::FracturedBone => Treatment {
+                name: "Splint",
+                price: Money::new(25, 0),
+                seconds: 90,
+            },
+            Self::Parasite => Treatment {
+                name: "Deworming",
+                price: Money::new(15, 0),
+                seconds: 45,
+            },
+            Self::Disease => Treatment {
+                name: "Antibiotics",
+                price: Money::new(30, 0),
+                seconds: 120,
+            },
+            Self::Trauma => Treatment {
+                name: "Quiet Den",
+                price: Money::new(20, 0),
+                seconds: 60,
+            },
+            Self::Poisoned => Treatment {
+                name: "Activated Charcoal",
+                price: Money::new(20, 0),
+                seconds: 40,
+            },
+        }
+    }
+}
 impl_enum_distribution!(ProblemType);
+/// How a [`ProblemType`] is fixed.
+pub(crate) struct Treatment {
+    pub(crate) name: &'static str,
+    pub(crate) price: Money,
+    pub(crate) seconds: u64,
+}
+/// Shows how long is left on a fox's treatment above its sprite.
+#[derive(Component)]
+struct TreatmentTimerUI;
+impl TreatmentTimerUI {
+    const FONT_SIZE: f32 = 10.;
+
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(
+        mut commands: Commands,
+        foxes_q: Query<(Entity, &Fox, Option<&Children>)>,
+        mut treatment_timer_uis_q: Query<&mut Text2d, With<Self>>,
+    ) {
+        let now = unix_seconds();
+        for (entity, fox, children) in &foxes_q {
+            let treatment_timer_ui = children
+                .into_iter()
+                .flatten()
+                .find(|child| treatment_timer_uis_q.contains(**child));
+            match (fox.treatment_seconds_left(now), treatment_timer_ui) {
+                (Some(seconds_left), Some(treatment_timer_ui)) => {
+                    let text = format!("{}:{:02}", seconds_left / 60, seconds_left % 60);
+                    if let Ok(mut treatment_timer_ui_text) =
+                        treatment_timer_uis_q.get_mut(*treatment_timer_ui)
+                    {
+                        if treatment_timer_ui_text.0 != text {
+                            treatment_timer_ui_text.0 = text;
+                        }
+                    }
+                }
+                (Some(_), None) => {
+                    commands.entity(entity).with_children(|fox| {
+                        fox.spawn((
+                            Self,
+                            Text2d::default(),
+                            TextFont::from_font_size(Self::FONT_SIZE),
+                            Transform::from_xyz(0., Fox::SIZE, 1.),
+                        ));
+                    });
+                }
+                (None, Some(treatment_timer_ui)) => {
+                    commands.entity(*treatment_timer_ui).despawn_recursive();
+                }
+                (None, None) => {}
+            }
+        }
+    }
+}
+/// The frames of a [`SpeciesData::sprite_sheet`], in order from left to right.
+///
+/// [`SpeciesData::sprite_sheet`]: species::SpeciesData::sprite_sheet
+#[derive(EnumCount, Clone, Copy, PartialEq, Eq)]
+enum FoxFrame {
+    Idle,
+    Crouch,
+    Airborne,
+    Land,
+}
+impl FoxFrame {
+    const SIZE: u32 = 100;
+
+    /// The frame for a jump that is `progress` of the way through, from `0.` to `1.`.
+    fn during(progress: f32) -> Self {
+        if progress < 0.2 {
+            Self::Crouch
+        } else if progress < 0.8 {
+            Self::Airborne
+        } else {
+            Self::Land
+        }
+    }
+}
+#[derive(Resource)]
+struct FoxSpriteSheetLayout(Handle<TextureAtlasLayout>);
+impl FoxSpriteSheetLayout {
+    #[allow(clippy::needless_pass_by_value)]
+    fn startup(
+        mut commands: Commands,
+        mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    ) {
+        commands.insert_resource(Self(texture_atlas_layouts.add(
+            TextureAtlasLayout::from_grid(
+                UVec2::splat(FoxFrame::SIZE),
+                FoxFrame::COUNT as u32,
+                1,
+                None,
+                None,
+            ),
+        )));
+    }
+}
 pub(crate) struct FoxPlugin;
 impl Plugin for FoxPlugin {
     fn build(&self, app: &mut bevy::app::App) {
-        app.add_systems(Update, Fox::jump_system);
+        app.insert_resource(FoxIds::default())
+            .add_systems(Startup, FoxSpriteSheetLayout::startup)
+            .add_systems(
+                Update,
+                (
+                    Fox::init,
+                    Fox::jump_system,
+                    Fox::animate_system,
+                    ActivityAnimation::system,
+                    TreatmentTimerUI::system,
+                ),
+            );
     }
 }
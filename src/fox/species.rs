@@ -0,0 +1,261 @@
+use std::{
+    fmt::{self, Display},
+    fs, io,
+    path::Path,
+};
+
+use once_cell::sync::OnceCell;
+use rand::distr::{weighted::WeightedIndex, Distribution, StandardUniform};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{fox::ActivityType, search::cell::Cell, Money, ASSETS_FOLDER};
+
+const SPECIES_PATH: &str = "species.ron";
+
+/// Every species, in the order they're listed in `assets/species.ron`, once [`load_species`] has
+/// read them.
+static SPECIES_REGISTRY: OnceCell<SpeciesRegistry> = OnceCell::new();
+
+/// Reads `assets/species.ron`, which has to happen before any [`FoxSpecies`] is used. Loading
+/// again does nothing.
+pub(crate) fn load_species() -> Result<(), SpeciesError> {
+    SPECIES_REGISTRY.get_or_try_init(|| {
+        let path = Path::new(ASSETS_FOLDER).join(SPECIES_PATH);
+        SpeciesRegistry::from_ron(&fs::read_to_string(path)?)
+    })?;
+    Ok(())
+}
+fn species_registry() -> &'static SpeciesRegistry {
+    SPECIES_REGISTRY
+        .get()
+        .expect("species are loaded before the game starts")
+}
+
+#[derive(Debug)]
+pub(crate) enum SpeciesError {
+    Io(io::Error),
+    Parse(ron::error::SpannedError),
+    Empty,
+    DuplicateId(String),
+    DuplicateCharacter(char),
+    ReservedCharacter(char),
+    NoPreferredActivities(String),
+    InvalidRarityWeights,
+}
+impl Display for SpeciesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read the species list: {err}"),
+            Self::Parse(err) => write!(f, "the species list is not valid RON: {err}"),
+            Self::Empty => write!(f, "there are no species"),
+            Self::DuplicateId(id) => write!(f, "more than one species has the id `{id}`"),
+            Self::DuplicateCharacter(character) => {
+                write!(f, "more than one species uses the character '{character}'")
+            }
+            Self::ReservedCharacter(character) => write!(
+                f,
+                "the character '{character}' is already used for empty cells or obstacles"
+            ),
+            Self::NoPreferredActivities(id) => {
+                write!(f, "the species `{id}` has no preferred activities")
+            }
+            Self::InvalidRarityWeights => write!(f, "every rarity weight is 0"),
+        }
+    }
+}
+impl std::error::Error for SpeciesError {}
+impl From<io::Error> for SpeciesError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+impl From<ron::error::SpannedError> for SpeciesError {
+    fn from(value: ron::error::SpannedError) -> Self {
+        Self::Parse(value)
+    }
+}
+
+/// What sets each [`FoxSpecies`] apart.
+#[derive(Deserialize)]
+pub(crate) struct SpeciesData {
+    /// The name saves refer to the species by.
+    pub(crate) id: String,
+    pub(crate) name: String,
+    /// The character level files use for the species, both for its foxes and its paw prints.
+    pub(crate) character: char,
+    pub(crate) paw_print: String,
+    pub(crate) sprite: String,
+    /// The frames a fox of this species is animated with inside a sanctuary.
+    pub(crate) sprite_sheet: String,
+    /// What a fox of this species earns before any activity or problem bonus.
+    pub(crate) base_income: Money,
+    /// How likely a fox is to be of this species, relative to the other species.
+    pub(crate) rarity_weight: u32,
+    /// The activities a fox of this species can have as its favorite.
+    pub(crate) preferred_activities: Vec<ActivityType>,
+}
+
+struct SpeciesRegistry {
+    species: Vec<SpeciesData>,
+    rarity_weights: WeightedIndex<u32>,
+}
+impl SpeciesRegistry {
+    fn from_ron(ron: &str) -> Result<Self, SpeciesError> {
+        let species: Vec<SpeciesData> = ron::from_str(ron)?;
+        if species.is_empty() {
+            return Err(SpeciesError::Empty);
+        }
+        for (i, species_data) in species.iter().enumerate() {
+            let earlier = &species[..i];
+            if earlier.iter().any(|earlier| earlier.id == species_data.id) {
+                return Err(SpeciesError::DuplicateId(species_data.id.clone()));
+            }
+            if earlier
+                .iter()
+                .any(|earlier| earlier.character == species_data.character)
+            {
+                return Err(SpeciesError::DuplicateCharacter(species_data.character));
+            }
+            if species_data.character == ' '
+                || Cell::OBSTACLE_CHARS.contains(&species_data.character)
+            {
+                return Err(SpeciesError::ReservedCharacter(species_data.character));
+            }
+            if species_data.preferred_activities.is_empty() {
+                return Err(SpeciesError::NoPreferredActivities(species_data.id.clone()));
+            }
+        }
+        let rarity_weights = WeightedIndex::new(
+            species
+                .iter()
+                .map(|species_data| species_data.rarity_weight),
+        )
+        .map_err(|_| SpeciesError::InvalidRarityWeights)?;
+        Ok(Self {
+            species,
+            rarity_weights,
+        })
+    }
+}
+
+/// A species from `assets/species.ron`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct FoxSpecies(usize);
+impl FoxSpecies {
+    pub(crate) fn all() -> impl Iterator<Item = Self> {
+        (0..species_registry().species.len()).map(Self)
+    }
+    pub(crate) fn data(self) -> &'static SpeciesData {
+        &species_registry().species[self.0]
+    }
+    pub(crate) fn from_id(id: &str) -> Option<Self> {
+        Self::all().find(|fox_species| fox_species.data().id == id)
+    }
+    pub(crate) fn from_character(character: char) -> Option<Self> {
+        Self::all().find(|fox_species| fox_species.data().character == character)
+    }
+}
+/// Samples a species by its [`SpeciesData::rarity_weight`], so rare species turn up less often.
+impl Distribution<FoxSpecies> for StandardUniform {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> FoxSpecies {
+        FoxSpecies(species_registry().rarity_weights.sample(rng))
+    }
+}
+impl Display for FoxSpecies {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.data().name)
+    }
+}
+// Species are written like the unit variants they used to be, so older saves still load.
+impl Serialize for FoxSpecies {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_unit_variant("FoxSpecies", self.0 as u32, &self.data().id)
+    }
+}
+impl<'de> Deserialize<'de> for FoxSpecies {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_enum("FoxSpecies", &[], FoxSpeciesVisitor)
+    }
+}
+struct FoxSpeciesVisitor;
+impl<'de> de::Visitor<'de> for FoxSpeciesVisitor {
+    type Value = FoxSpecies;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a fox species id")
+    }
+    fn visit_str<E: de::Error>(self, id: &str) -> Result<Self::Value, E> {
+        FoxSpecies::from_id(id).ok_or_else(|| E::custom(format!("unknown fox species `{id}`")))
+    }
+    fn visit_enum<A: de::EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        let (fox_species, variant) = data.variant_seed(FoxSpeciesId)?;
+        de::VariantAccess::unit_variant(variant)?;
+        Ok(fox_species)
+    }
+}
+/// Reads the variant name of a [`FoxSpecies`] written as an enum.
+struct FoxSpeciesId;
+impl<'de> de::DeserializeSeed<'de> for FoxSpeciesId {
+    type Value = FoxSpecies;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_identifier(FoxSpeciesVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn committed_species_load() {
+        load_species().unwrap();
+        for species_data in &species_registry().species {
+            for image in [
+                &species_data.paw_print,
+                &species_data.sprite,
+                &species_data.sprite_sheet,
+            ] {
+                assert!(
+                    Path::new("assets").join(image).is_file(),
+                    "{} refers to the missing image {image}",
+                    species_data.id
+                );
+            }
+        }
+    }
+    /// A species list with one species, which is valid apart from anything passed in.
+    fn one_species(character: char, preferred_activities: &str) -> String {
+        format!(
+            r#"[(
+                id: "Test",
+                name: "Test",
+                character: '{character}',
+                paw_print: "",
+                sprite: "",
+                sprite_sheet: "",
+                base_income: "0.10",
+                rarity_weight: 1,
+                preferred_activities: [{preferred_activities}],
+            )]"#
+        )
+    }
+
+    #[test]
+    fn species_characters_must_not_be_obstacles() {
+        assert!(matches!(
+            SpeciesRegistry::from_ron(&one_species('s', "Digging")),
+            Err(SpeciesError::ReservedCharacter('s'))
+        ));
+    }
+    #[test]
+    fn species_need_a_preferred_activity() {
+        assert!(SpeciesRegistry::from_ron(&one_species('T', "Digging")).is_ok());
+        assert!(matches!(
+            SpeciesRegistry::from_ron(&one_species('T', "")),
+            Err(SpeciesError::NoPreferredActivities(id)) if id == "Test"
+        ));
+    }
+}
@@ -1,8 +1,13 @@
 #![allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
 
+use std::{
+    env,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
 use app_state::AppStatePlugin;
 use bevy::{
-    app::{App, Startup, Update},
+    app::{App, AppExit, Startup, Update},
     asset::AssetServer,
     core_pipeline::core_2d::Camera2d,
     ecs::{
@@ -18,9 +23,10 @@ use bevy::{
     DefaultPlugins,
 };
 use clickable::{Clickable, ClickablePlugin};
-use fox::FoxPlugin;
+use fox::{species::load_species, FoxPlugin};
 use merge::MergePlugin;
-use money::Money;
+use money::{Money, MoneyPlugin};
+use save::{LoadResources, SaveFile, SavePlugin};
 use search::SearchPlugin;
 use ui::UIPlugin;
 
@@ -57,6 +63,7 @@ pub mod clickable;
 pub mod fox;
 pub mod merge;
 pub mod money;
+pub mod save;
 pub mod search;
 pub mod ui;
 
@@ -89,9 +96,19 @@ impl FollowMouse {
         }
     }
 }
+pub(crate) const ASSETS_FOLDER: &str = "assets";
+
 pub(crate) type Optional<'w, D, F = ()> = Option<Single<'w, D, F>>;
 
-fn main() {
+fn main() -> AppExit {
+    if let Err(err) = load_species() {
+        eprintln!("Could not load the fox species: {err}");
+        return AppExit::error();
+    }
+    if env::args().any(|arg| arg == "--validate-levels") {
+        return search::validate::validate_levels();
+    }
+
     let mut app = App::new();
     app.add_plugins((
         DefaultPlugins,
@@ -101,24 +118,30 @@ fn main() {
         FoxPlugin,
         MergePlugin,
         SearchPlugin,
+        SavePlugin,
+        MoneyPlugin,
     ));
-    app.insert_resource(Money::default());
     app.add_systems(Startup, startup)
         .add_systems(Update, FollowMouse::system);
-    app.run();
+    app.run()
 }
 #[allow(clippy::needless_pass_by_value)]
 fn startup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut windows_q: Query<&mut Window, With<PrimaryWindow>>,
+    mut load_resources: LoadResources,
 ) {
     let mut window = windows_q.single_mut();
     window.mode = WindowMode::BorderlessFullscreen(MonitorSelection::Current);
 
     commands.spawn(Camera2d);
 
-    merge::fox_lot::FoxLot::spawn_grid(&mut commands, &asset_server, -1..=1, -1..=1);
+    if let Some(save_file) = SaveFile::load() {
+        save_file.spawn(&mut commands, &asset_server, &mut load_resources);
+    } else {
+        merge::fox_lot::FoxLot::spawn_grid(&mut commands, &asset_server, -1..=1, -1..=1);
+    }
 }
 fn mouse_world_coordinates(
     window: &Window,
@@ -131,6 +154,23 @@ fn mouse_world_coordinates(
             .ok()
     })
 }
+/// Seconds since the Unix epoch, which unlike [`Time`](bevy::time::Time) carries on between
+/// sessions.
+pub(crate) fn unix_seconds() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+/// The time of day `unix_seconds` falls on, in UTC.
+pub(crate) fn time_of_day_string(unix_seconds: u64) -> String {
+    let seconds = unix_seconds % (24 * 60 * 60);
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / (60 * 60),
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
 fn point_in_bounds(point: Vec2, top_left: Vec2, size: &Size) -> bool {
     point.x >= top_left.x
         && point.x <= top_left.x + size.0.x
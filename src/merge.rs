@@ -1,17 +1,42 @@
 use bevy::{
-    app::{App, Plugin},
+    app::{App, Plugin, PostUpdate, Update},
     ecs::{
-        schedule::IntoSystemConfigs,
-        system::{Query, ResMut, Resource},
+        event::{Event, EventWriter},
+        query::{Changed, With},
+        schedule::{Condition, IntoSystemConfigs},
+        system::{Query, Res, ResMut, Resource, SystemParam},
     },
-    state::state::OnEnter,
+    state::condition::in_state,
+    time::{Time, Timer, TimerMode},
 };
+use care::CarePlugin;
+use enrichment::{EnrichmentPlugin, HeldEnrichmentItem};
+use fox_drag::FoxDragPlugin;
 use fox_lot::{FoxLotPlugin, FoxSanctuary};
+use holding_pen::HoldingPenPlugin;
+use inspection::InspectionPlugin;
+use overview::OverviewPlugin;
+use placement::{PlacementPlugin, PlacementTray};
+use release::ReleasePlugin;
 use ui::UIPlugin;
 
-use crate::{app_state::AppState, search, Money};
+use crate::{
+    app_state::AppState,
+    clickable::Hovered,
+    fox::Fox,
+    money::{TransactionReason, Wallet},
+    Money,
+};
 
+pub mod care;
+pub mod enrichment;
+pub mod fox_drag;
 pub mod fox_lot;
+pub mod holding_pen;
+pub mod inspection;
+pub mod overview;
+pub mod placement;
+pub mod release;
 pub mod ui;
 
 #[derive(Resource, Debug)]
@@ -21,14 +46,31 @@ impl Default for Income {
         Self(Money::ZERO)
     }
 }
+/// How often [`Income`] is credited to [`Money`] while in [`AppState::Merge`].
+#[derive(Resource)]
+pub(crate) struct IncomeTimer(Timer);
+impl IncomeTimer {
+    const DEFAULT_SECONDS: f32 = 5.;
+
+    pub(crate) fn new(seconds: f32) -> Self {
+        Self(Timer::from_seconds(seconds, TimerMode::Repeating))
+    }
+}
+impl Default for IncomeTimer {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SECONDS)
+    }
+}
+#[derive(Event, Debug)]
+pub(crate) struct IncomePaidEvent(pub(crate) Money);
 #[derive(Resource, Default)]
 pub(crate) struct FoxStorageInfo {
     pub(crate) total_foxes: u32,
-    total_capacity: u32,
+    pub(crate) total_capacity: u32,
 }
 impl FoxStorageInfo {
     pub(crate) const fn remaining_capacity(&self) -> u32 {
-        self.total_capacity - self.total_foxes
+        self.total_capacity.saturating_sub(self.total_foxes)
     }
 }
 
@@ -37,20 +79,72 @@ impl Plugin for MergePlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(FoxStorageInfo::default())
             .insert_resource(Income::default())
-            .add_plugins((UIPlugin, FoxLotPlugin))
+            .insert_resource(IncomeTimer::default())
+            .add_event::<IncomePaidEvent>()
+            .add_plugins((
+                UIPlugin,
+                FoxLotPlugin,
+                CarePlugin,
+                EnrichmentPlugin,
+                ReleasePlugin,
+                HoldingPenPlugin,
+                PlacementPlugin,
+                FoxDragPlugin,
+                InspectionPlugin,
+                OverviewPlugin,
+            ))
+            .add_systems(Update, pay_income.run_if(in_state(AppState::Merge)))
+            // After every Update system, so foxes despawned by one are already gone
             .add_systems(
-                OnEnter(AppState::Merge),
-                calculate_income.after(search::exit),
+                PostUpdate,
+                calculate_income.run_if(fox_sanctuaries_changed.or(foxes_changed)),
             );
     }
 }
+/// What the cursor is carrying or pointing at in the Merge view.
+#[derive(SystemParam)]
+pub(crate) struct CursorCargo<'w, 's> {
+    held_enrichment_item: Res<'w, HeldEnrichmentItem>,
+    placement_tray: Res<'w, PlacementTray>,
+    hovered_foxes_q: Query<'w, 's, (), (With<Fox>, With<Hovered>)>,
+}
+impl CursorCargo<'_, '_> {
+    /// Whether the cursor holds an enrichment item or a fox from the placement tray.
+    pub(crate) fn is_carrying_item(&self) -> bool {
+        self.held_enrichment_item.0.is_some() || self.placement_tray.is_dragging()
+    }
+    /// Whether clicking a sanctuary should pick it up or buy it, rather than doing something with
+    /// what the cursor carries or with the fox it's over.
+    pub(crate) fn can_click_sanctuary(&self) -> bool {
+        !self.is_carrying_item() && self.hovered_foxes_q.is_empty()
+    }
+}
 #[allow(clippy::needless_pass_by_value)]
-fn calculate_income(mut income: ResMut<Income>, fox_sanctuaries_q: Query<&FoxSanctuary>) {
+fn fox_sanctuaries_changed(fox_sanctuaries_q: Query<(), Changed<FoxSanctuary>>) -> bool {
+    !fox_sanctuaries_q.is_empty()
+}
+#[allow(clippy::needless_pass_by_value)]
+fn foxes_changed(foxes_q: Query<(), Changed<Fox>>) -> bool {
+    !foxes_q.is_empty()
+}
+#[allow(clippy::needless_pass_by_value)]
+fn calculate_income(mut income: ResMut<Income>, foxes_q: Query<&Fox>) {
     income.0 = Money::ZERO;
-    for fox_sanctuary in &fox_sanctuaries_q {
-        for fox in &fox_sanctuary.foxes {
-            income.0 += fox.income();
-        }
+    for fox in &foxes_q {
+        income.0 += fox.income();
+    }
+}
+#[allow(clippy::needless_pass_by_value)]
+fn pay_income(
+    time: Res<Time>,
+    income: Res<Income>,
+    mut income_timer: ResMut<IncomeTimer>,
+    mut wallet: Wallet,
+    mut income_paid_events: EventWriter<IncomePaidEvent>,
+) {
+    income_timer.0.tick(time.delta());
+    if income_timer.0.just_finished() && income.0 != Money::ZERO {
+        wallet.credit(&income.0, TransactionReason::IncomePayout);
+        income_paid_events.send(IncomePaidEvent(income.0.clone()));
     }
-    println!("New income: {income:?}");
 }
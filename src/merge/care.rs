@@ -0,0 +1,316 @@
+use bevy::{
+    app::{App, Plugin, Update},
+    color::{
+        palettes::tailwind::{STONE_600, STONE_800},
+        Alpha, Color,
+    },
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::{Changed, With},
+        schedule::{Condition, IntoSystemConfigs},
+        system::{Commands, Query, ResMut, Single, SystemParam},
+    },
+    hierarchy::{BuildChildren, ChildBuild, ChildBuilder, DespawnRecursiveExt, Parent},
+    state::condition::in_state,
+    text::TextFont,
+    ui::{
+        widget::{Button, Text},
+        AlignItems, BackgroundColor, Display, FlexDirection, Interaction, Node, PositionType,
+        UiRect, Val,
+    },
+    utils::default,
+};
+
+use crate::{
+    app_state::AppState,
+    fox::{Fox, ProblemSlot, ProblemState},
+    money::{TransactionReason, Wallet},
+    unix_seconds,
+};
+
+use super::{
+    fox_lot::FoxSanctuary,
+    fox_sanctuaries_changed, foxes_changed,
+    release::{ReleasedFox, ReleasedFoxes},
+    FoxStorageInfo,
+};
+
+#[derive(Component)]
+pub(super) struct CareButton;
+impl CareButton {
+    const FONT_SIZE: f32 = 30.;
+
+    pub(super) fn spawn(top_container: &mut ChildBuilder<'_>) {
+        top_container.spawn((
+            Self,
+            Button,
+            Text::new("Care"),
+            TextFont::from_font_size(Self::FONT_SIZE),
+        ));
+    }
+}
+/// Lists every fox that still has a problem to examine or treat, or is ready to be released.
+/// Hidden until the [`CareButton`] is pressed.
+#[derive(Component)]
+pub(super) struct CarePanel;
+impl CarePanel {
+    const FONT_SIZE: f32 = 24.;
+
+    pub(super) fn spawn(top_container: &mut ChildBuilder<'_>) {
+        top_container.spawn((
+            Self,
+            Node {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                top: Val::Percent(100.),
+                right: Val::Px(10.),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.)),
+                row_gap: Val::Px(8.),
+                ..default()
+            },
+            BackgroundColor(Color::Srgba(STONE_800).with_alpha(0.9)),
+        ));
+    }
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(
+        mut commands: Commands,
+        care_panel: Single<Entity, With<Self>>,
+        foxes_q: Query<(Entity, &Fox)>,
+    ) {
+        let now = unix_seconds();
+        let mut care_panel = commands.entity(*care_panel);
+        care_panel.despawn_descendants();
+        care_panel.with_children(|care_panel| {
+            let mut needs_care = false;
+            for (fox_entity, fox) in &foxes_q {
+                let problem_states = ProblemSlot::ALL
+                    .map(|problem_slot| (problem_slot, fox.problem_state(problem_slot, now)));
+                // An unknown problem can always be examined once the ones before it are known
+                let has_care_action = fox.is_rehabilitated()
+                    || problem_states.iter().any(|&(_, problem_state)| {
+                        matches!(
+                            problem_state,
+                            ProblemState::Unknown | ProblemState::Untreated(_)
+                        )
+                    });
+                if has_care_action {
+                    needs_care = true;
+                    Self::spawn_fox_row(care_panel, fox, fox_entity, &problem_states);
+                }
+            }
+            if !needs_care {
+                care_panel.spawn((
+                    Text::new(if foxes_q.is_empty() {
+                        "No foxes yet"
+                    } else {
+                        "No foxes need care"
+                    }),
+                    TextFont::from_font_size(Self::FONT_SIZE),
+                ));
+            }
+        });
+    }
+    fn spawn_fox_row(
+        care_panel: &mut ChildBuilder<'_>,
+        fox: &Fox,
+        fox_entity: Entity,
+        problem_states: &[(ProblemSlot, ProblemState)],
+    ) {
+        care_panel
+            .spawn(Node {
+                flex_direction: FlexDirection::Column,
+                ..default()
+            })
+            .with_children(|fox_row| {
+                fox_row.spawn((
+                    Text::new(format!("{} ({})", fox.name(), fox.species())),
+                    TextFont::from_font_size(Self::FONT_SIZE),
+                ));
+                // Problems are examined in order, so only the first unknown one can be examined
+                let mut can_examine = true;
+                for &(problem_slot, problem_state) in problem_states {
+                    fox_row
+                        .spawn(Node {
+                            column_gap: Val::Px(10.),
+                            align_items: AlignItems::Center,
+                            ..default()
+                        })
+                        .with_children(|problem_row| {
+                            let care_action = match problem_state {
+                                ProblemState::Unknown => can_examine.then_some(CareAction::Examine),
+                                ProblemState::Untreated(_) => Some(CareAction::Treat(problem_slot)),
+                                ProblemState::Treating { .. } | ProblemState::Fixed(_) => None,
+                            };
+                            problem_row.spawn((
+                                Text::new(format!("{problem_slot}: {problem_state}")),
+                                TextFont::from_font_size(Self::FONT_SIZE),
+                            ));
+                            if let Some(care_action) = care_action {
+                                CareActionButton::spawn(
+                                    problem_row,
+                                    fox_entity,
+                                    care_action,
+                                    &care_action.label(problem_state),
+                                );
+                            }
+                        });
+                    if problem_state == ProblemState::Unknown {
+                        can_examine = false;
+                    }
+                }
+                if fox.is_rehabilitated() {
+                    CareActionButton::spawn(
+                        fox_row,
+                        fox_entity,
+                        CareAction::Release,
+                        &format!("Release (+${})", fox.release_reward()),
+                    );
+                }
+            });
+    }
+}
+#[derive(Clone, Copy)]
+enum CareAction {
+    Examine,
+    Treat(ProblemSlot),
+    Release,
+}
+impl CareAction {
+    fn label(self, problem_state: ProblemState) -> String {
+        match (self, problem_state) {
+            (Self::Treat(_), ProblemState::Untreated(problem_type)) => {
+                let treatment = problem_type.treatment();
+                format!(
+                    "{} (${}, {}:{:02})",
+                    treatment.name,
+                    treatment.price,
+                    treatment.seconds / 60,
+                    treatment.seconds % 60
+                )
+            }
+            _ => "Examine".to_owned(),
+        }
+    }
+}
+#[derive(Component)]
+struct CareActionButton {
+    /// The [`Fox`] the action is for.
+    fox_entity: Entity,
+    care_action: CareAction,
+}
+#[derive(SystemParam)]
+struct CareActionResources<'w, 's> {
+    commands: Commands<'w, 's>,
+    wallet: Wallet<'w>,
+    fox_storage_info: ResMut<'w, FoxStorageInfo>,
+    released_foxes: ResMut<'w, ReleasedFoxes>,
+    fox_sanctuaries_q: Query<'w, 's, &'static mut FoxSanctuary>,
+    foxes_q: Query<'w, 's, (&'static mut Fox, &'static Parent)>,
+}
+impl CareActionButton {
+    const FONT_SIZE: f32 = 20.;
+
+    fn spawn(row: &mut ChildBuilder<'_>, fox_entity: Entity, care_action: CareAction, label: &str) {
+        row.spawn((
+            Self {
+                fox_entity,
+                care_action,
+            },
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(6.), Val::Px(2.)),
+                ..default()
+            },
+            BackgroundColor(Color::from(STONE_600)),
+            Text::new(label),
+            TextFont::from_font_size(Self::FONT_SIZE),
+        ));
+    }
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(
+        mut resources: CareActionResources,
+        care_action_buttons_q: Query<(&Interaction, &Self), Changed<Interaction>>,
+    ) {
+        for (interaction, care_action_button) in &care_action_buttons_q {
+            if *interaction != Interaction::Pressed {
+                continue;
+            }
+            let fox_entity = care_action_button.fox_entity;
+            let Ok((mut fox, parent)) = resources.foxes_q.get_mut(fox_entity) else {
+                continue;
+            };
+            match care_action_button.care_action {
+                CareAction::Release => {
+                    if !fox.is_rehabilitated() {
+                        continue;
+                    }
+                    let Ok(mut fox_sanctuary) = resources.fox_sanctuaries_q.get_mut(parent.get())
+                    else {
+                        continue;
+                    };
+                    let reward = fox.release_reward();
+                    fox_sanctuary.remove_fox(&mut resources.commands, fox_entity, &fox);
+                    resources.fox_storage_info.total_foxes -= 1;
+                    resources.wallet.credit(&reward, TransactionReason::Release);
+                    resources
+                        .released_foxes
+                        .0
+                        .push(ReleasedFox::new(&fox, reward));
+                }
+                CareAction::Examine => {
+                    fox.examine();
+                }
+                CareAction::Treat(problem_slot) => {
+                    let now = unix_seconds();
+                    if let ProblemState::Untreated(problem_type) =
+                        fox.problem_state(problem_slot, now)
+                    {
+                        if resources
+                            .wallet
+                            .try_spend(
+                                &problem_type.treatment().price,
+                                TransactionReason::Treatment,
+                            )
+                            .is_err()
+                        {
+                            continue;
+                        }
+                        fox.start_treatment(problem_slot, now);
+                    }
+                }
+            }
+        }
+    }
+}
+/// Fixes the problems whose treatments are done, in every state since treatments carry on while
+/// the player is searching.
+#[allow(clippy::needless_pass_by_value)]
+fn finish_treatments(mut foxes_q: Query<&mut Fox>) {
+    let now = unix_seconds();
+    for mut fox in &mut foxes_q {
+        // Only take the fox mutably when something is done, so income isn't recalculated every
+        // frame
+        if fox.treatment_seconds_left(now) == Some(0) {
+            fox.finish_treatments(now);
+        }
+    }
+}
+
+pub(super) struct CarePlugin;
+impl Plugin for CarePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                finish_treatments,
+                CareActionButton::system.run_if(in_state(AppState::Merge)),
+                CarePanel::system
+                    .after(finish_treatments)
+                    .after(CareActionButton::system)
+                    .run_if(fox_sanctuaries_changed.or(foxes_changed)),
+            ),
+        );
+    }
+}
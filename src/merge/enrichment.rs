@@ -0,0 +1,346 @@
+use std::fmt::Display;
+
+use bevy::{
+    app::{App, Plugin, Update},
+    color::{
+        palettes::tailwind::{
+            AMBER_300, AMBER_700, LIME_500, SKY_400, STONE_400, STONE_600, STONE_800, YELLOW_300,
+            YELLOW_900,
+        },
+        Alpha, Color, Srgba,
+    },
+    ecs::{
+        change_detection::DetectChanges,
+        component::Component,
+        entity::Entity,
+        event::EventReader,
+        query::{Changed, With},
+        schedule::IntoSystemConfigs,
+        system::{Commands, Query, Res, ResMut, Resource, SystemParam},
+        world::Ref,
+    },
+    hierarchy::{BuildChildren, ChildBuild, ChildBuilder, Children, DespawnRecursiveExt, Parent},
+    math::Vec2,
+    sprite::Sprite,
+    state::condition::in_state,
+    text::{Text2d, TextColor, TextFont},
+    transform::components::Transform,
+    ui::{
+        widget::{Button, Text},
+        BackgroundColor, Display as NodeDisplay, FlexDirection, Interaction, Node, PositionType,
+        UiRect, Val,
+    },
+    utils::default,
+};
+use serde::{Deserialize, Serialize};
+use strum::EnumCount;
+use strum_macros::{EnumCount, FromRepr};
+
+use crate::{
+    app_state::AppState,
+    clickable::ClickableSet,
+    fox::{ActivityType, Fox},
+    money::{TransactionReason, Wallet},
+    Money,
+};
+
+use super::fox_lot::{FoxSanctuary, FoxSanctuaryMouseupEvent, MIN_FOX_POSITION};
+
+/// Something placed in a [`FoxSanctuary`] that satisfies every fox there whose favorite
+/// [`ActivityType`] it's for.
+#[derive(Debug, FromRepr, EnumCount, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u32)]
+pub(crate) enum EnrichmentItem {
+    LeafPile,
+    DigBox,
+    ToyBall,
+    PuzzleFeeder,
+    Tunnel,
+    LogMaze,
+    SunRock,
+}
+impl EnrichmentItem {
+    const SIZE: f32 = 18.;
+    const LABEL_FONT_SIZE: f32 = 8.;
+    const SPACING: f32 = 22.;
+
+    fn all() -> impl Iterator<Item = Self> {
+        (0..Self::COUNT as u32).filter_map(Self::from_repr)
+    }
+    pub(crate) const fn activity_type(self) -> ActivityType {
+        match self {
+            Self::LeafPile => ActivityType::Pouncing,
+            Self::DigBox => ActivityType::Digging,
+            Self::ToyBall => ActivityType::Playing,
+            Self::PuzzleFeeder => ActivityType::Hunting,
+            Self::Tunnel => ActivityType::Tunneling,
+            Self::LogMaze => ActivityType::Exploring,
+            Self::SunRock => ActivityType::Sunbathing,
+        }
+    }
+    pub(crate) const fn price(self) -> Money {
+        match self {
+            Self::LeafPile | Self::ToyBall => Money::new(15, 0),
+            Self::DigBox | Self::PuzzleFeeder => Money::new(25, 0),
+            Self::Tunnel | Self::LogMaze => Money::new(40, 0),
+            Self::SunRock => Money::new(30, 0),
+        }
+    }
+    const fn color(self) -> Srgba {
+        match self {
+            Self::LeafPile => AMBER_700,
+            Self::DigBox => YELLOW_900,
+            Self::ToyBall => SKY_400,
+            Self::PuzzleFeeder => LIME_500,
+            Self::Tunnel => STONE_400,
+            Self::LogMaze => AMBER_300,
+            Self::SunRock => YELLOW_300,
+        }
+    }
+    fn spawn(self, fox_sanctuary: &mut ChildBuilder<'_>, slot: usize) {
+        // Items sit in a row along the bottom edge, below where the foxes roam
+        fox_sanctuary
+            .spawn((
+                EnrichmentItemSprite,
+                Sprite::from_color(Color::from(self.color()), Vec2::splat(Self::SIZE)),
+                Transform::from_xyz(
+                    MIN_FOX_POSITION.x + Self::SIZE / 2. + slot as f32 * Self::SPACING,
+                    MIN_FOX_POSITION.y - Self::SIZE / 2.,
+                    0.5,
+                ),
+            ))
+            .with_children(|enrichment_item_sprite| {
+                enrichment_item_sprite.spawn((
+                    Text2d::new(self.to_string()),
+                    TextFont::from_font_size(Self::LABEL_FONT_SIZE),
+                    TextColor::BLACK,
+                    Transform::from_xyz(0., 0., 0.1),
+                ));
+            });
+    }
+}
+impl Display for EnrichmentItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::LeafPile => "Leaf Pile",
+                Self::DigBox => "Dig Box",
+                Self::ToyBall => "Toy Ball",
+                Self::PuzzleFeeder => "Puzzle Feeder",
+                Self::Tunnel => "Tunnel",
+                Self::LogMaze => "Log Maze",
+                Self::SunRock => "Sun Rock",
+            }
+        )
+    }
+}
+#[derive(Component)]
+struct EnrichmentItemSprite;
+/// The item bought from the [`EnrichmentPanel`] that will be placed in the next
+/// [`FoxSanctuary`] clicked.
+#[derive(Resource, Default)]
+pub(crate) struct HeldEnrichmentItem(pub(crate) Option<EnrichmentItem>);
+
+#[derive(Component)]
+pub(super) struct EnrichmentButton;
+impl EnrichmentButton {
+    const FONT_SIZE: f32 = 30.;
+
+    pub(super) fn spawn(top_container: &mut ChildBuilder<'_>) {
+        top_container.spawn((
+            Self,
+            Button,
+            Text::new("Enrichment"),
+            TextFont::from_font_size(Self::FONT_SIZE),
+        ));
+    }
+}
+/// Sells the [`EnrichmentItem`]s. Hidden until the [`EnrichmentButton`] is pressed.
+#[derive(Component)]
+pub(super) struct EnrichmentPanel;
+impl EnrichmentPanel {
+    const FONT_SIZE: f32 = 24.;
+
+    pub(super) fn spawn(top_container: &mut ChildBuilder<'_>) {
+        top_container
+            .spawn((
+                Self,
+                Node {
+                    display: NodeDisplay::None,
+                    position_type: PositionType::Absolute,
+                    top: Val::Percent(100.),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(10.)),
+                    row_gap: Val::Px(4.),
+                    ..default()
+                },
+                BackgroundColor(Color::Srgba(STONE_800).with_alpha(0.9)),
+            ))
+            .with_children(|enrichment_panel| {
+                enrichment_panel.spawn((
+                    Text::new("Buy an item, then click a sanctuary to place it"),
+                    TextFont::from_font_size(Self::FONT_SIZE),
+                ));
+                for enrichment_item in EnrichmentItem::all() {
+                    EnrichmentItemButton::spawn(enrichment_panel, enrichment_item);
+                }
+            });
+    }
+}
+#[derive(Component)]
+struct EnrichmentItemButton(EnrichmentItem);
+impl EnrichmentItemButton {
+    const FONT_SIZE: f32 = 20.;
+    const COLOR: Srgba = STONE_600;
+    const HELD_COLOR: Srgba = LIME_500;
+
+    fn spawn(enrichment_panel: &mut ChildBuilder<'_>, enrichment_item: EnrichmentItem) {
+        enrichment_panel.spawn((
+            Self(enrichment_item),
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(6.), Val::Px(2.)),
+                ..default()
+            },
+            BackgroundColor(Color::from(Self::COLOR)),
+            Text::new(format!(
+                "{enrichment_item} (${}): {}",
+                enrichment_item.price(),
+                enrichment_item.activity_type()
+            )),
+            TextFont::from_font_size(Self::FONT_SIZE),
+        ));
+    }
+    /// Picks up the pressed item, or puts it back if it was already held.
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(
+        mut held_enrichment_item: ResMut<HeldEnrichmentItem>,
+        enrichment_item_buttons_q: Query<(&Interaction, &Self), Changed<Interaction>>,
+    ) {
+        for (interaction, enrichment_item_button) in &enrichment_item_buttons_q {
+            if *interaction == Interaction::Pressed {
+                held_enrichment_item.0 = if held_enrichment_item.0 == Some(enrichment_item_button.0)
+                {
+                    None
+                } else {
+                    Some(enrichment_item_button.0)
+                };
+            }
+        }
+    }
+    #[allow(clippy::needless_pass_by_value)]
+    fn highlight(
+        held_enrichment_item: Res<HeldEnrichmentItem>,
+        mut enrichment_item_buttons_q: Query<(&Self, &mut BackgroundColor)>,
+    ) {
+        for (enrichment_item_button, mut background_color) in &mut enrichment_item_buttons_q {
+            background_color.0 = Color::from(
+                if held_enrichment_item.0 == Some(enrichment_item_button.0) {
+                    Self::HELD_COLOR
+                } else {
+                    Self::COLOR
+                },
+            );
+        }
+    }
+}
+#[derive(SystemParam)]
+struct PlaceEnrichmentItemResources<'w> {
+    wallet: Wallet<'w>,
+    held_enrichment_item: ResMut<'w, HeldEnrichmentItem>,
+}
+/// Buys the held item for the clicked sanctuary, if it has room and doesn't have one already.
+#[allow(clippy::needless_pass_by_value)]
+fn place_enrichment_item(
+    mut resources: PlaceEnrichmentItemResources,
+    mut fox_sanctuary_mouseup_events: EventReader<FoxSanctuaryMouseupEvent>,
+    mut fox_sanctuaries_q: Query<&mut FoxSanctuary>,
+) {
+    for ev in fox_sanctuary_mouseup_events.read() {
+        let Some(enrichment_item) = resources.held_enrichment_item.0 else {
+            continue;
+        };
+        if let Ok(mut fox_sanctuary) = fox_sanctuaries_q.get_mut(ev.0) {
+            if !fox_sanctuary.can_place(enrichment_item)
+                || resources
+                    .wallet
+                    .try_spend(&enrichment_item.price(), TransactionReason::Enrichment)
+                    .is_err()
+            {
+                continue;
+            }
+            fox_sanctuary.items.push(enrichment_item);
+            resources.held_enrichment_item.0 = None;
+        }
+    }
+}
+/// Redraws each changed sanctuary's items.
+#[allow(clippy::needless_pass_by_value)]
+fn draw_enrichment_items(
+    mut commands: Commands,
+    fox_sanctuaries_q: Query<(Entity, &FoxSanctuary, Option<&Children>), Changed<FoxSanctuary>>,
+    enrichment_item_sprites_q: Query<(), With<EnrichmentItemSprite>>,
+) {
+    for (entity, fox_sanctuary, children) in &fox_sanctuaries_q {
+        for &child in children.into_iter().flatten() {
+            if enrichment_item_sprites_q.contains(child) {
+                commands.entity(child).despawn_recursive();
+            }
+        }
+        let items = fox_sanctuary.items.clone();
+        commands.entity(entity).with_children(|fox_sanctuary| {
+            for (slot, enrichment_item) in items.iter().enumerate() {
+                enrichment_item.spawn(fox_sanctuary, slot);
+            }
+        });
+    }
+}
+/// Satisfies each fox whose sanctuary has an item for its favorite activity, rechecking when the
+/// sanctuary changes or the fox moves into another.
+#[allow(clippy::needless_pass_by_value)]
+fn satisfy_foxes(
+    fox_sanctuaries_q: Query<Ref<FoxSanctuary>>,
+    mut foxes_q: Query<(&mut Fox, Ref<Parent>)>,
+) {
+    for (mut fox, parent) in &mut foxes_q {
+        let Ok(fox_sanctuary) = fox_sanctuaries_q.get(parent.get()) else {
+            continue;
+        };
+        if !fox_sanctuary.is_changed() && !parent.is_changed() {
+            continue;
+        }
+        let satisfied = fox_sanctuary
+            .items
+            .iter()
+            .any(|item| item.activity_type() == fox.favorite_activity_type());
+        // Only write back when it differs, so income isn't recalculated for nothing
+        if fox.is_satisfied() != satisfied {
+            fox.set_satisfied(satisfied);
+        }
+    }
+}
+
+pub(super) struct EnrichmentPlugin;
+impl Plugin for EnrichmentPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(HeldEnrichmentItem::default())
+            .add_systems(
+                Update,
+                (
+                    (
+                        EnrichmentItemButton::system,
+                        EnrichmentItemButton::highlight,
+                    )
+                        .chain(),
+                    place_enrichment_item.after(ClickableSet),
+                )
+                    .run_if(in_state(AppState::Merge)),
+            );
+        app.add_systems(
+            Update,
+            (draw_enrichment_items, satisfy_foxes).after(place_enrichment_item),
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use bevy::{
+    app::{App, Plugin, PostUpdate, Update},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventReader},
+        query::{Added, With},
+        schedule::{Condition, IntoSystemConfigs},
+        system::{Commands, Query, Res, Single, SystemParam},
+    },
+    hierarchy::Parent,
+    input::{common_conditions::input_just_released, mouse::MouseButton},
+    math::{Vec2, Vec3},
+    render::camera::Camera,
+    state::condition::in_state,
+    time::Time,
+    transform::components::{GlobalTransform, Transform},
+    window::{PrimaryWindow, Window},
+};
+
+use crate::{
+    app_state::AppState,
+    clickable::{Clickable, ClickableSet, Hovered},
+    fox::Fox,
+    mouse_world_coordinates, Size,
+};
+
+use super::{fox_lot::FoxSanctuary, CursorCargo};
+
+/// A fox the player has picked up out of its [`FoxSanctuary`], which stays a child of that
+/// sanctuary until it's dropped.
+#[derive(Component)]
+pub(crate) enum FoxDrag {
+    Following {
+        home: Vec3,
+    },
+    /// Dropped somewhere it can't go, so sliding back to where it was picked up.
+    Returning {
+        from: Vec3,
+        home: Vec3,
+        time_since_start: f32,
+    },
+}
+impl FoxDrag {
+    const RETURN_TIME: f32 = 0.25;
+    /// How far above the other foxes a picked up fox is drawn.
+    const Z_OFFSET: f32 = 5.;
+
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(
+        mut commands: Commands,
+        time: Res<Time>,
+        window: Single<&Window, With<PrimaryWindow>>,
+        camera_q: Single<(&Camera, &GlobalTransform)>,
+        mut fox_drags_q: Query<(Entity, &mut Self, &mut Transform, &Parent)>,
+        parent_gtransforms_q: Query<&GlobalTransform>,
+    ) {
+        let (camera, camera_transform) = *camera_q;
+        let mouse_coordinates = mouse_world_coordinates(&window, camera, camera_transform);
+        for (entity, mut fox_drag, mut transform, parent) in &mut fox_drags_q {
+            match *fox_drag {
+                Self::Following { home } => {
+                    let (Some(mouse_coordinates), Ok(parent_gtransform)) =
+                        (mouse_coordinates, parent_gtransforms_q.get(parent.get()))
+                    else {
+                        continue;
+                    };
+                    // The fox is still a child of its sanctuary, so follow the mouse in the
+                    // sanctuary's space
+                    let local_mouse_coordinates = parent_gtransform
+                        .affine()
+                        .inverse()
+                        .transform_point3(mouse_coordinates.extend(0.));
+                    transform.translation = local_mouse_coordinates
+                        .truncate()
+                        .extend(home.z + Self::Z_OFFSET);
+                }
+                Self::Returning {
+                    from,
+                    home,
+                    ref mut time_since_start,
+                } => {
+                    *time_since_start += time.delta_secs();
+                    let progress = (*time_since_start / Self::RETURN_TIME).min(1.);
+                    transform.translation = from.lerp(home, progress);
+                    if progress >= 1. {
+                        commands.entity(entity).remove::<Self>();
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Event, Debug)]
+struct FoxMousedownEvent(Entity);
+#[derive(Event, Debug)]
+pub(super) struct FoxMouseupEvent(pub(super) Entity);
+
+/// Lets newly spawned foxes be picked up and clicked.
+#[allow(clippy::needless_pass_by_value)]
+fn make_foxes_draggable(mut commands: Commands, foxes_q: Query<Entity, Added<Fox>>) {
+    for entity in &foxes_q {
+        commands.entity(entity).insert((
+            Clickable::new()
+                .set_mousedown_event(FoxMousedownEvent)
+                .set_mouseup_event(FoxMouseupEvent),
+            Size(Vec2::splat(Fox::SIZE)),
+        ));
+    }
+}
+#[allow(clippy::needless_pass_by_value)]
+fn pick_up_fox(
+    mut commands: Commands,
+    cursor_cargo: CursorCargo,
+    mut fox_mousedown_events: EventReader<FoxMousedownEvent>,
+    mut foxes_q: Query<&mut Transform, (With<Fox>, With<Parent>)>,
+) {
+    for ev in fox_mousedown_events.read() {
+        if cursor_cargo.is_carrying_item() {
+            continue;
+        }
+        let Ok(mut transform) = foxes_q.get_mut(ev.0) else {
+            continue;
+        };
+        let mut fox_commands = commands.entity(ev.0);
+        Fox::interrupt(&mut fox_commands, &mut transform);
+        fox_commands.insert(FoxDrag::Following {
+            home: transform.translation,
+        });
+        // Only the one fox, when several overlap
+        break;
+    }
+}
+#[derive(SystemParam)]
+struct DropFoxResources<'w, 's> {
+    hovered_fox_sanctuaries_q: Query<'w, 's, Entity, (With<FoxSanctuary>, With<Hovered>)>,
+    fox_sanctuaries_q: Query<'w, 's, &'static mut FoxSanctuary>,
+}
+/// Moves the dropped fox into the sanctuary it's dropped on if that has room, and otherwise sends
+/// it back to where it was picked up.
+#[allow(clippy::needless_pass_by_value)]
+fn drop_fox(
+    mut commands: Commands,
+    mut resources: DropFoxResources,
+    mut fox_drags_q: Query<(Entity, &Fox, &mut FoxDrag, &mut Transform, &Parent)>,
+) {
+    for (entity, fox, mut fox_drag, mut transform, parent) in &mut fox_drags_q {
+        let FoxDrag::Following { home } = *fox_drag else {
+            continue;
+        };
+        let origin = parent.get();
+        let target = resources
+            .hovered_fox_sanctuaries_q
+            .iter()
+            .find(|&fox_sanctuary| fox_sanctuary != origin);
+        if let Some(target) = target {
+            if let Ok([mut origin_fox_sanctuary, mut target_fox_sanctuary]) =
+                resources.fox_sanctuaries_q.get_many_mut([origin, target])
+            {
+                if target_fox_sanctuary.has_room() {
+                    origin_fox_sanctuary.move_fox(
+                        &mut commands,
+                        &mut target_fox_sanctuary,
+                        target,
+                        entity,
+                        fox,
+                    );
+                    transform.translation = FoxSanctuary::random_fox_translation();
+                    commands.entity(entity).remove::<FoxDrag>();
+                    continue;
+                }
+            }
+        }
+        *fox_drag = FoxDrag::Returning {
+            from: transform.translation,
+            home,
+            time_since_start: 0.,
+        };
+    }
+}
+
+pub(super) struct FoxDragPlugin;
+impl Plugin for FoxDragPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<FoxMousedownEvent>()
+            .add_event::<FoxMouseupEvent>()
+            .add_systems(
+                Update,
+                (
+                    make_foxes_draggable,
+                    pick_up_fox.after(ClickableSet),
+                    FoxDrag::system.before(ClickableSet),
+                )
+                    .run_if(in_state(AppState::Merge)),
+            )
+            // After every Update system, like dropping a fox from the placement tray, so the
+            // fox is still held for all of them
+            .add_systems(
+                PostUpdate,
+                drop_fox
+                    .run_if(in_state(AppState::Merge).and(input_just_released(MouseButton::Left))),
+            );
+    }
+}
@@ -1,28 +1,23 @@
-use std::{
-    fmt::Display,
-    ops::{RangeInclusive, SubAssign},
-};
+use std::{fmt::Display, ops::RangeInclusive};
 
 use bevy::{
     app::{App, Plugin, Update},
-    asset::AssetServer,
-    color::{
-        palettes::{css::BLACK, tailwind::GRAY_300},
-        Color, Srgba,
-    },
+    asset::{AssetServer, Handle},
+    color::{palettes::css::BLACK, Color, Srgba},
     ecs::{
         component::Component,
         entity::Entity,
         event::{Event, EventReader},
-        query::{Changed, With, Without},
+        query::{Added, With, Without},
         schedule::{common_conditions::resource_changed, IntoSystemConfigs},
-        system::{Commands, Query, Res, ResMut, Resource, Single},
+        system::{Commands, Query, Res, ResMut, Resource, Single, SystemParam},
     },
     hierarchy::{BuildChildren, ChildBuild, ChildBuilder, Children, DespawnRecursiveExt, Parent},
+    image::Image,
     math::{Vec2, Vec3},
     sprite::Sprite,
     state::condition::in_state,
-    text::{JustifyText, Text2d, TextBounds, TextColor, TextFont, TextLayout},
+    text::{JustifyText, Text2d, TextColor, TextFont, TextLayout},
     transform::{commands::BuildChildrenTransformExt, components::Transform},
     utils::default,
 };
@@ -32,7 +27,9 @@ use rand::Rng;
 use crate::{
     app_state::{AppState, Merge},
     clickable::{Clickable, ClickableSet, Hovered},
-    fox::Fox,
+    fox::{Fox, FoxId},
+    merge::{enrichment::EnrichmentItem, CursorCargo},
+    money::{TransactionReason, Wallet},
     FollowMouse, Money, Optional, Size,
 };
 
@@ -43,24 +40,31 @@ static MARGIN_SIZE: Lazy<Vec2> = Lazy::new(|| *SIZE + FoxLot::MARGIN);
 static INNER_PADDING_SIZE: Lazy<Vec2> = Lazy::new(|| *SIZE - FoxLot::PADDING);
 pub(crate) static MAX_FOX_POSITION: Lazy<Vec2> = Lazy::new(|| *INNER_PADDING_SIZE * 0.5);
 pub(crate) static MIN_FOX_POSITION: Lazy<Vec2> = Lazy::new(|| -*MAX_FOX_POSITION);
+static PRICE_CONTAINER_TRANSLATION: Lazy<Vec3> =
+    Lazy::new(|| Vec3::new(0., -INNER_PADDING_SIZE.y * 0.5, 2.));
 #[derive(Component)]
-pub(crate) struct FoxLot;
+pub(crate) struct FoxLot {
+    x: i32,
+    y: i32,
+}
 impl FoxLot {
     const SIZE: f32 = 150.;
     const MARGIN: f32 = 10.;
     const PADDING: f32 = 45.;
 
-    fn spawn(
+    pub(crate) fn spawn_at_grid_pos(
         commands: &mut Commands,
         asset_server: &Res<AssetServer>,
-        translation: Vec3,
-        level: u32,
+        x: i32,
+        y: i32,
+        fox_sanctuary: FoxSanctuary,
+        foxes: Vec<Fox>,
     ) {
         commands
             .spawn((
-                Self,
+                Self { x, y },
                 Merge,
-                Transform::from_translation(translation),
+                Transform::from_xyz(x as f32 * MARGIN_SIZE.x, y as f32 * MARGIN_SIZE.y, 0.),
                 Sprite {
                     image: asset_server.load("images/fox-lot.png"),
                     custom_size: Some(*SIZE),
@@ -68,27 +72,9 @@ impl FoxLot {
                 },
             ))
             .with_children(|fox_lot| {
-                FoxSanctuary::spawn(fox_lot, asset_server, level);
+                fox_sanctuary.spawn(fox_lot, asset_server, foxes);
             });
     }
-    fn spawn_at_grid_pos(
-        commands: &mut Commands,
-        asset_server: &Res<AssetServer>,
-        x: i32,
-        y: i32,
-        level: u32,
-    ) {
-        Self::spawn(
-            commands,
-            asset_server,
-            Vec3 {
-                x: x as f32 * MARGIN_SIZE.x,
-                y: y as f32 * MARGIN_SIZE.y,
-                z: 0.,
-            },
-            level,
-        );
-    }
     pub(crate) fn spawn_grid(
         commands: &mut Commands,
         asset_server: &Res<AssetServer>,
@@ -97,32 +83,47 @@ impl FoxLot {
     ) {
         for y in y_s {
             for x in x_s.clone() {
-                Self::spawn_at_grid_pos(commands, asset_server, x, y, 0);
+                Self::spawn_at_grid_pos(commands, asset_server, x, y, FoxSanctuary::new(0), vec![]);
             }
         }
     }
+    pub(crate) const fn grid_pos(&self) -> (i32, i32) {
+        (self.x, self.y)
+    }
 }
 #[derive(Component)]
 pub(crate) struct FoxSanctuary {
     level: u32,
-    pub(crate) foxes: Vec<Fox>,
+    /// The foxes living here, each a [`Fox`] entity among the sanctuary's children that holds
+    /// all of the fox's data. This list, not the children, decides which foxes belong here.
+    foxes: Vec<FoxId>,
+    pub(crate) items: Vec<EnrichmentItem>,
 }
 impl FoxSanctuary {
     pub(crate) const CAPACITY_PER_LEVEL: u32 = 10;
+    const MAX_ITEMS: usize = 4;
 
-    const fn new(level: u32) -> Self {
+    pub(crate) const fn new(level: u32) -> Self {
         Self {
             level,
             foxes: vec![],
+            items: vec![],
         }
     }
-    fn spawn(fox_lot: &mut ChildBuilder<'_>, asset_server: &Res<AssetServer>, level: u32) {
+    fn spawn(
+        mut self,
+        fox_lot: &mut ChildBuilder<'_>,
+        asset_server: &Res<AssetServer>,
+        foxes: Vec<Fox>,
+    ) {
+        let level = self.level;
+        self.foxes = foxes.iter().map(Fox::id).collect();
         fox_lot
             .spawn((
-                Self::new(level),
+                self,
                 Transform::from_xyz(0., 0., 1.),
                 Sprite {
-                    image: asset_server.load(format!("images/fox{level}.png")),
+                    image: Self::image(asset_server, level),
                     custom_size: Some(*SIZE),
                     ..default()
                 },
@@ -132,15 +133,28 @@ impl FoxSanctuary {
                 Size(*SIZE),
             ))
             .with_children(|fox_sanctuary| {
-                PriceContainer::spawn(
-                    fox_sanctuary,
-                    asset_server,
-                    Vec3::new(0., -INNER_PADDING_SIZE.y * 0.5, 2.),
-                );
+                if level == 0 {
+                    PriceContainer::spawn(
+                        fox_sanctuary,
+                        asset_server,
+                        *PRICE_CONTAINER_TRANSLATION,
+                    );
+                }
+                for fox in foxes {
+                    fox.spawn(fox_sanctuary, Self::random_fox_translation());
+                }
             });
     }
+    fn image(asset_server: &Res<AssetServer>, level: u32) -> Handle<Image> {
+        asset_server.load(format!("images/fox{level}.png"))
+    }
+    /// Doubles with every level after the first, so merging two sanctuaries keeps all of their
+    /// room.
     pub(crate) const fn capacity(&self) -> u32 {
-        self.level * Self::CAPACITY_PER_LEVEL
+        match self.level {
+            0 => 0,
+            level => Self::CAPACITY_PER_LEVEL.saturating_mul(2u32.saturating_pow(level - 1)),
+        }
     }
     pub(crate) fn has_room(&self) -> bool {
         (self.foxes.len() as u32) < self.capacity()
@@ -148,19 +162,67 @@ impl FoxSanctuary {
     pub(crate) const fn level(&self) -> u32 {
         self.level
     }
+    pub(crate) fn fox_ids(&self) -> &[FoxId] {
+        &self.foxes
+    }
+    fn item_capacity(&self) -> usize {
+        (self.level as usize).min(Self::MAX_ITEMS)
+    }
+    pub(crate) fn can_place(&self, enrichment_item: EnrichmentItem) -> bool {
+        self.items.len() < self.item_capacity() && !self.items.contains(&enrichment_item)
+    }
+    /// Whether `other` can be merged into `self`, which requires both to share the same
+    /// non-zero level. The next level holds as many foxes as both, so they always fit.
+    const fn can_absorb(&self, other: &Self) -> bool {
+        self.level != 0 && self.level == other.level
+    }
+    /// Moves every fox and item from `other` into `self` and levels `self` up, leaving `other`
+    /// as an empty lot. Returns the items `self` already has or has no room for.
+    fn absorb(&mut self, other: &mut Self) -> Vec<EnrichmentItem> {
+        self.foxes.append(&mut other.foxes);
+        self.level += 1;
+        other.level = 0;
+        let mut leftover_items = vec![];
+        for enrichment_item in other.items.drain(..) {
+            if self.can_place(enrichment_item) {
+                self.items.push(enrichment_item);
+            } else {
+                leftover_items.push(enrichment_item);
+            }
+        }
+        leftover_items
+    }
     pub(crate) fn push_fox(&mut self, commands: &mut Commands, self_entity: Entity, fox: Fox) {
-        let mut rng = rand::rng();
+        self.foxes.push(fox.id());
         commands.entity(self_entity).with_children(|fox_sanctuary| {
-            fox.spawn(
-                fox_sanctuary,
-                Vec3::new(
-                    rng.random_range(MIN_FOX_POSITION.x..MAX_FOX_POSITION.x),
-                    rng.random_range(MIN_FOX_POSITION.y..MAX_FOX_POSITION.y),
-                    1.,
-                ),
-            );
+            fox.spawn(fox_sanctuary, Self::random_fox_translation());
         });
-        self.foxes.push(fox);
+    }
+    /// Takes a fox out of the sanctuary and despawns it.
+    pub(crate) fn remove_fox(&mut self, commands: &mut Commands, fox_entity: Entity, fox: &Fox) {
+        commands.entity(fox_entity).despawn_recursive();
+        self.foxes.retain(|&fox_id| fox_id != fox.id());
+    }
+    /// Moves a fox into the `target` sanctuary, reparenting it to `target_entity`.
+    pub(crate) fn move_fox(
+        &mut self,
+        commands: &mut Commands,
+        target: &mut Self,
+        target_entity: Entity,
+        fox_entity: Entity,
+        fox: &Fox,
+    ) {
+        commands.entity(fox_entity).set_parent(target_entity);
+        self.foxes.retain(|&fox_id| fox_id != fox.id());
+        target.foxes.push(fox.id());
+    }
+    pub(crate) fn random_fox_translation() -> Vec3 {
+        let mut rng = rand::rng();
+        Vec3::new(
+            rng.random_range(MIN_FOX_POSITION.x..MAX_FOX_POSITION.x),
+            rng.random_range(MIN_FOX_POSITION.y..MAX_FOX_POSITION.y),
+            1.,
+        )
     }
 }
 #[derive(Component)]
@@ -184,26 +246,10 @@ impl PriceContainer {
                 Transform::from_translation(translation),
             ))
             .with_children(|price_container| {
-                PriceContainerCoin::spawn(price_container, &asset_server);
+                PriceContainerCoin::spawn(price_container, asset_server);
                 PriceContainerText::spawn(price_container);
             });
     }
-    #[allow(clippy::needless_pass_by_value)]
-    fn update_text(
-        price_containers_q: Query<&Children, With<Self>>,
-        price_container_texts_q: Query<
-            (&Parent, &TextBounds, &Transform),
-            (With<PriceContainerText>, Changed<Text2d>),
-        >,
-        price_container_coins_q: Query<(&Sprite, &Transform), With<PriceContainerCoin>>,
-    ) -> f32 {
-        for (price_container_text_parent, text_bounds, price_container_text_transform) in
-            &price_container_texts_q
-        {
-            let price_container = price_containers_q.get(price_container_text_parent.get());
-        }
-        0.
-    }
 }
 #[derive(Component)]
 struct PriceContainerCoin;
@@ -234,6 +280,15 @@ impl PriceContainerText {
         ));
     }
 
+    #[allow(clippy::needless_pass_by_value)]
+    fn init(
+        mut fox_lot_price_ui_q: Query<&mut Text2d, Added<Self>>,
+        fox_lot_price: Res<FoxLotPrice>,
+    ) {
+        for mut fox_lot_price_ui in &mut fox_lot_price_ui_q {
+            fox_lot_price_ui.0 = fox_lot_price.to_string();
+        }
+    }
     #[allow(clippy::needless_pass_by_value)]
     fn update(
         mut fox_lot_price_ui_q: Query<&mut Text2d, With<Self>>,
@@ -245,7 +300,7 @@ impl PriceContainerText {
     }
 }
 #[derive(Resource)]
-pub(crate) struct FoxLotPrice(Money);
+pub(crate) struct FoxLotPrice(pub(crate) Money);
 mod fox_lot_price_statics {
     use crate::Money;
 
@@ -266,7 +321,7 @@ impl Display for FoxLotPrice {
 #[derive(Event, Debug)]
 struct FoxSanctuaryMousedownEvent(Entity);
 #[derive(Event, Debug)]
-struct FoxSanctuaryMouseupEvent(Entity);
+pub(crate) struct FoxSanctuaryMouseupEvent(pub(crate) Entity);
 
 pub struct FoxLotPlugin;
 impl Plugin for FoxLotPlugin {
@@ -281,6 +336,7 @@ impl Plugin for FoxLotPlugin {
                         .before(ClickableSet)
                         .before(FollowMouse::system)),
                     (mousedown_fox_sanctuary, buy_fox_sanctuary).after(ClickableSet),
+                    PriceContainerText::init,
                     PriceContainerText::update.run_if(resource_changed::<FoxLotPrice>),
                 )
                     .run_if(in_state(AppState::Merge)),
@@ -290,10 +346,14 @@ impl Plugin for FoxLotPlugin {
 #[allow(clippy::needless_pass_by_value)]
 fn mousedown_fox_sanctuary(
     mut commands: Commands,
+    cursor_cargo: CursorCargo,
     mut fox_lot_mousedown_events: EventReader<FoxSanctuaryMousedownEvent>,
     mut fox_sanctuaries_q: Query<(Entity, &Parent, &FoxSanctuary, &Transform)>,
 ) {
     for ev in fox_lot_mousedown_events.read() {
+        if !cursor_cargo.can_click_sanctuary() {
+            continue;
+        }
         if let Ok((entity, parent, fox_sanctuary, transform)) = fox_sanctuaries_q.get_mut(ev.0) {
             if fox_sanctuary.level != 0 {
                 // Select Fox Sanctuary
@@ -308,35 +368,47 @@ fn mousedown_fox_sanctuary(
         }
     }
 }
+#[derive(SystemParam)]
+struct BuyFoxSanctuaryResources<'w, 's> {
+    asset_server: Res<'w, AssetServer>,
+    wallet: Wallet<'w>,
+    fox_lot_price: ResMut<'w, FoxLotPrice>,
+    fox_storage_info: ResMut<'w, FoxStorageInfo>,
+    cursor_cargo: CursorCargo<'w, 's>,
+}
 #[allow(clippy::needless_pass_by_value)]
 fn buy_fox_sanctuary(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut money: ResMut<Money>,
-    mut fox_lot_price: ResMut<FoxLotPrice>,
-    mut fox_storage_info: ResMut<FoxStorageInfo>,
+    mut resources: BuyFoxSanctuaryResources,
     mut fox_sanctuary_mouseup_events: EventReader<FoxSanctuaryMouseupEvent>,
     mut fox_sanctuaries_q: Query<(&mut FoxSanctuary, &mut Sprite, &Children)>,
     price_containers_q: Query<Entity, With<PriceContainer>>,
 ) {
     for ev in fox_sanctuary_mouseup_events.read() {
-        if money.ge(&fox_lot_price.0) {
-            if let Ok((mut fox_sanctuary, mut fox_sanctuary_sprite, fox_sanctuary_children)) =
-                fox_sanctuaries_q.get_mut(ev.0)
-            {
-                // Buy Fox Sanctuary
-                if fox_sanctuary.level == 0 {
-                    money.sub_assign(fox_lot_price.0.clone());
-                    fox_lot_price.0 += &*fox_lot_price_statics::BASE_PRICE;
-                    fox_sanctuary.level += 1;
-                    fox_sanctuary_sprite.image =
-                        asset_server.load(format!("images/fox{}.png", fox_sanctuary.level));
-                    fox_storage_info.total_capacity += FoxSanctuary::CAPACITY_PER_LEVEL;
+        if !resources.cursor_cargo.can_click_sanctuary() {
+            continue;
+        }
+        if let Ok((mut fox_sanctuary, mut fox_sanctuary_sprite, fox_sanctuary_children)) =
+            fox_sanctuaries_q.get_mut(ev.0)
+        {
+            // Buy Fox Sanctuary
+            if fox_sanctuary.level == 0 {
+                if resources
+                    .wallet
+                    .try_spend(&resources.fox_lot_price.0, TransactionReason::LotPurchase)
+                    .is_err()
+                {
+                    continue;
+                }
+                resources.fox_lot_price.0 += &*fox_lot_price_statics::BASE_PRICE;
+                fox_sanctuary.level += 1;
+                fox_sanctuary_sprite.image =
+                    FoxSanctuary::image(&resources.asset_server, fox_sanctuary.level);
+                resources.fox_storage_info.total_capacity += FoxSanctuary::CAPACITY_PER_LEVEL;
 
-                    for &child in fox_sanctuary_children {
-                        if let Ok(price_container) = price_containers_q.get(child) {
-                            commands.entity(price_container).despawn_recursive();
-                        }
+                for &child in fox_sanctuary_children {
+                    if let Ok(price_container) = price_containers_q.get(child) {
+                        commands.entity(price_container).despawn_recursive();
                     }
                 }
             }
@@ -345,13 +417,21 @@ fn buy_fox_sanctuary(
 }
 type HoveredTypeSanctuaryData<'a> = (Entity, &'a Parent, &'a mut Transform);
 type HoveredTypeSanctuaryFilter = (With<FoxSanctuary>, With<Hovered>, Without<FollowMouse>);
-//type HoveredFoxSanctuary =
+#[derive(SystemParam)]
+struct SelectFoxSanctuaryResources<'w> {
+    asset_server: Res<'w, AssetServer>,
+    wallet: Wallet<'w>,
+    fox_storage_info: ResMut<'w, FoxStorageInfo>,
+}
 #[allow(clippy::needless_pass_by_value)]
 fn select_fox_sanctuary(
     mut commands: Commands,
+    mut resources: SelectFoxSanctuaryResources,
     mut fox_sanctuary_mouseup_events: EventReader<FoxSanctuaryMouseupEvent>,
     mut fox_sanctuaries_q: Query<(Entity, &mut Transform, &FollowMouse)>,
     hovered_fox_sanctuary_q: Optional<HoveredTypeSanctuaryData, HoveredTypeSanctuaryFilter>,
+    mut fox_sanctuary_data_q: Query<(&mut FoxSanctuary, &mut Sprite, Option<&Children>)>,
+    foxes_q: Query<(), With<Fox>>,
 ) {
     let mut hovered_fox_sanctuary_q = hovered_fox_sanctuary_q.map(Single::into_inner);
 
@@ -361,16 +441,60 @@ fn select_fox_sanctuary(
         {
             // Move Fox Sanctuary
             let follow_parent = follow_mouse.parent.unwrap();
-            let mut entity_commands = commands.entity(entity);
+            let mut return_to_lot = true;
             if let Some((hovered_entity, hovered_parent, ref mut hovered_transform)) =
                 hovered_fox_sanctuary_q
             {
-                entity_commands.set_parent(hovered_parent.get());
-                commands.entity(hovered_entity).set_parent(follow_parent);
-                *fox_sanctuary_transform = **hovered_transform;
-                **hovered_transform = follow_mouse.previous_transform;
-            } else {
-                entity_commands.set_parent(follow_parent);
+                if let Ok([selected_data, hovered_data]) =
+                    fox_sanctuary_data_q.get_many_mut([entity, hovered_entity])
+                {
+                    let (mut fox_sanctuary, mut fox_sanctuary_sprite, fox_sanctuary_children) =
+                        selected_data;
+                    let (mut hovered_fox_sanctuary, mut hovered_sprite, _) = hovered_data;
+                    if hovered_fox_sanctuary.can_absorb(&fox_sanctuary) {
+                        // Merge Fox Sanctuaries
+                        let previous_capacity =
+                            fox_sanctuary.capacity() + hovered_fox_sanctuary.capacity();
+                        // Items that don't fit in the merged sanctuary are refunded
+                        for enrichment_item in hovered_fox_sanctuary.absorb(&mut fox_sanctuary) {
+                            resources
+                                .wallet
+                                .credit(&enrichment_item.price(), TransactionReason::Refund);
+                        }
+                        resources.fox_storage_info.total_capacity -= previous_capacity;
+                        resources.fox_storage_info.total_capacity +=
+                            hovered_fox_sanctuary.capacity();
+
+                        hovered_sprite.image = FoxSanctuary::image(
+                            &resources.asset_server,
+                            hovered_fox_sanctuary.level,
+                        );
+                        fox_sanctuary_sprite.image =
+                            FoxSanctuary::image(&resources.asset_server, fox_sanctuary.level);
+                        for &child in fox_sanctuary_children.into_iter().flatten() {
+                            if foxes_q.contains(child) {
+                                commands.entity(child).set_parent(hovered_entity);
+                            }
+                        }
+                        commands.entity(entity).with_children(|fox_sanctuary| {
+                            PriceContainer::spawn(
+                                fox_sanctuary,
+                                &resources.asset_server,
+                                *PRICE_CONTAINER_TRANSLATION,
+                            );
+                        });
+                    } else if hovered_fox_sanctuary.level != fox_sanctuary.level {
+                        // Swap Fox Sanctuaries
+                        commands.entity(entity).set_parent(hovered_parent.get());
+                        commands.entity(hovered_entity).set_parent(follow_parent);
+                        *fox_sanctuary_transform = **hovered_transform;
+                        **hovered_transform = follow_mouse.previous_transform;
+                        return_to_lot = false;
+                    }
+                }
+            }
+            if return_to_lot {
+                commands.entity(entity).set_parent(follow_parent);
                 *fox_sanctuary_transform = follow_mouse.previous_transform;
             }
             commands
@@ -380,3 +504,21 @@ fn select_fox_sanctuary(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merging_keeps_the_capacity_of_both_sanctuaries() {
+        for level in 1..=6 {
+            let mut fox_sanctuary = FoxSanctuary::new(level);
+            let mut other = FoxSanctuary::new(level);
+            let previous_capacity = fox_sanctuary.capacity() + other.capacity();
+            assert!(fox_sanctuary.can_absorb(&other));
+            fox_sanctuary.absorb(&mut other);
+            assert_eq!(fox_sanctuary.capacity(), previous_capacity);
+            assert_eq!(other.capacity(), 0);
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use std::cmp::Ordering;
+
+use bevy::{
+    app::{App, Plugin, Update},
+    color::{palettes::tailwind::RED_900, Alpha, Color},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::With,
+        schedule::{common_conditions::resource_changed, IntoSystemConfigs},
+        system::{Commands, Query, Res, ResMut, Resource, Single},
+    },
+    hierarchy::{BuildChildren, ChildBuild, ChildBuilder, DespawnRecursiveExt},
+    state::condition::in_state,
+    text::TextFont,
+    ui::{widget::Text, BackgroundColor, Display, FlexDirection, Node, UiRect, Val},
+    utils::default,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{app_state::AppState, fox::Fox, unix_seconds};
+
+use super::{fox_lot::FoxSanctuary, FoxStorageInfo};
+
+/// Puts as many of `foxes` as there is room for into the highest level sanctuaries, returning
+/// the ones that didn't fit.
+pub(crate) fn place_foxes(
+    commands: &mut Commands,
+    fox_storage_info: &mut FoxStorageInfo,
+    fox_sanctuaries_q: &mut Query<(Entity, &mut FoxSanctuary)>,
+    mut foxes: Vec<Fox>,
+) -> Vec<Fox> {
+    while !foxes.is_empty() {
+        let mut best_sanctuaries = vec![];
+        for fox_sanctuary in &mut *fox_sanctuaries_q {
+            if !fox_sanctuary.1.has_room() {
+                continue;
+            }
+            if best_sanctuaries.is_empty() {
+                best_sanctuaries = vec![fox_sanctuary];
+            } else {
+                match fox_sanctuary.1.level().cmp(&best_sanctuaries[0].1.level()) {
+                    Ordering::Greater => best_sanctuaries = vec![fox_sanctuary],
+                    Ordering::Equal => best_sanctuaries.push(fox_sanctuary),
+                    Ordering::Less => {}
+                }
+            }
+        }
+        if best_sanctuaries.is_empty() {
+            break;
+        }
+        let mut current_sanctuary = 0;
+        while !foxes.is_empty() && current_sanctuary < best_sanctuaries.len() {
+            if best_sanctuaries[current_sanctuary].1.has_room() {
+                let entity = best_sanctuaries[current_sanctuary].0;
+                best_sanctuaries[current_sanctuary].1.push_fox(
+                    commands,
+                    entity,
+                    foxes.pop().unwrap(),
+                );
+                fox_storage_info.total_foxes += 1;
+            } else {
+                current_sanctuary += 1;
+            }
+        }
+    }
+    foxes
+}
+
+/// A caught fox waiting in the [`HoldingPen`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct HeldFox {
+    pub(crate) fox: Fox,
+    /// When the fox next loses happiness, in [`unix_seconds`].
+    next_happiness_loss_at: u64,
+}
+/// Caught foxes that didn't fit in any [`FoxSanctuary`], oldest first. They move into the
+/// sanctuaries as soon as there's room, and lose happiness the longer they wait.
+#[derive(Resource, Default)]
+pub(crate) struct HoldingPen(pub(crate) Vec<HeldFox>);
+impl HoldingPen {
+    /// How long a fox can wait before it starts losing happiness.
+    const GRACE_SECONDS: u64 = 2 * 60;
+    /// How often a fox loses happiness once its grace period is over.
+    const HAPPINESS_LOSS_SECONDS: u64 = 60;
+
+    pub(crate) fn hold(&mut self, fox: Fox, now: u64) {
+        self.0.push(HeldFox {
+            fox,
+            next_happiness_loss_at: now + Self::GRACE_SECONDS,
+        });
+    }
+    /// Moves the longest waiting foxes into any sanctuaries with room.
+    #[allow(clippy::needless_pass_by_value)]
+    fn rehouse(
+        mut commands: Commands,
+        mut holding_pen: ResMut<Self>,
+        mut fox_storage_info: ResMut<FoxStorageInfo>,
+        mut fox_sanctuaries_q: Query<(Entity, &mut FoxSanctuary)>,
+    ) {
+        let room = fox_storage_info.remaining_capacity() as usize;
+        if holding_pen.0.is_empty() || room == 0 {
+            return;
+        }
+        let now = unix_seconds();
+        let moving = room.min(holding_pen.0.len());
+        // Reversed, since `place_foxes` places from the back
+        let foxes = holding_pen
+            .0
+            .drain(..moving)
+            .rev()
+            .map(|held_fox| held_fox.fox)
+            .collect();
+        for fox in place_foxes(
+            &mut commands,
+            &mut fox_storage_info,
+            &mut fox_sanctuaries_q,
+            foxes,
+        ) {
+            holding_pen.hold(fox, now);
+        }
+    }
+    /// Takes happiness from every fox that has waited too long, in every state since foxes keep
+    /// waiting while the player is searching.
+    #[allow(clippy::needless_pass_by_value)]
+    fn neglect(mut holding_pen: ResMut<Self>) {
+        let now = unix_seconds();
+        // Only take the pen mutably when a fox loses happiness, so the UI isn't rebuilt every
+        // frame
+        if !holding_pen
+            .0
+            .iter()
+            .any(|held_fox| held_fox.next_happiness_loss_at <= now)
+        {
+            return;
+        }
+        for held_fox in &mut holding_pen.0 {
+            while held_fox.next_happiness_loss_at <= now {
+                held_fox.fox.lose_happiness();
+                held_fox.next_happiness_loss_at += Self::HAPPINESS_LOSS_SECONDS;
+            }
+        }
+    }
+}
+
+/// Lists the foxes in the [`HoldingPen`]. Hidden while it's empty.
+#[derive(Component)]
+pub(super) struct HoldingPenPanel;
+impl HoldingPenPanel {
+    const FONT_SIZE: f32 = 24.;
+
+    pub(super) fn spawn(top_container: &mut ChildBuilder<'_>) {
+        top_container.spawn((
+            Self,
+            Node {
+                display: Display::None,
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.)),
+                row_gap: Val::Px(4.),
+                ..default()
+            },
+            BackgroundColor(Color::Srgba(RED_900).with_alpha(0.9)),
+        ));
+    }
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(
+        mut commands: Commands,
+        holding_pen: Res<HoldingPen>,
+        holding_pen_panel: Single<(Entity, &mut Node), With<Self>>,
+    ) {
+        let (holding_pen_panel, mut node) = holding_pen_panel.into_inner();
+        node.display = if holding_pen.0.is_empty() {
+            Display::None
+        } else {
+            Display::Flex
+        };
+        let mut holding_pen_panel = commands.entity(holding_pen_panel);
+        holding_pen_panel.despawn_descendants();
+        holding_pen_panel.with_children(|holding_pen_panel| {
+            holding_pen_panel.spawn((
+                Text::new(format!(
+                    "{} fox{} waiting for room in a sanctuary",
+                    holding_pen.0.len(),
+                    if holding_pen.0.len() == 1 { "" } else { "es" }
+                )),
+                TextFont::from_font_size(Self::FONT_SIZE),
+            ));
+            for held_fox in &holding_pen.0 {
+                holding_pen_panel.spawn((
+                    Text::new(format!(
+                        "{} ({}), happiness {}",
+                        held_fox.fox.name(),
+                        held_fox.fox.species(),
+                        held_fox.fox.happiness()
+                    )),
+                    TextFont::from_font_size(Self::FONT_SIZE),
+                ));
+            }
+        });
+    }
+}
+
+pub(super) struct HoldingPenPlugin;
+impl Plugin for HoldingPenPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(HoldingPen::default()).add_systems(
+            Update,
+            (
+                HoldingPen::neglect,
+                HoldingPen::rehouse
+                    .after(HoldingPen::neglect)
+                    .run_if(in_state(AppState::Merge)),
+                HoldingPenPanel::system
+                    .after(HoldingPen::rehouse)
+                    .run_if(resource_changed::<HoldingPen>),
+            ),
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use bevy::{
+    app::{App, Plugin, Update},
+    color::{
+        palettes::tailwind::{STONE_600, STONE_800},
+        Alpha, Color,
+    },
+    ecs::{
+        change_detection::DetectChanges,
+        component::Component,
+        entity::Entity,
+        event::EventReader,
+        query::{Changed, With},
+        schedule::IntoSystemConfigs,
+        system::{Commands, Query, ResMut, Resource, Single},
+        world::Ref,
+    },
+    hierarchy::{BuildChildren, ChildBuild, ChildBuilder, DespawnRecursiveExt},
+    state::condition::in_state,
+    text::TextFont,
+    ui::{
+        widget::{Button, Text},
+        AlignItems, BackgroundColor, Display, FlexDirection, Interaction, JustifyContent, Node,
+        PositionType, UiRect, Val,
+    },
+    utils::default,
+};
+
+use crate::{
+    app_state::AppState,
+    clickable::{ClickableSet, Hovered},
+    fox::{Fox, ProblemSlot},
+    unix_seconds,
+};
+
+use super::fox_drag::FoxMouseupEvent;
+
+/// Which fox the [`FoxCard`] is about.
+#[derive(Resource, Default)]
+struct InspectedFox {
+    /// The fox last clicked, shown whenever no other fox is hovered.
+    pinned: Option<Entity>,
+    /// The fox the card currently shows.
+    shown: Option<Entity>,
+    /// Whether the card was drawn for the pinned fox, and so has an [`UnpinButton`].
+    shown_pinned: bool,
+}
+/// Pins the card to the clicked fox.
+#[allow(clippy::needless_pass_by_value)]
+fn pin_fox(
+    mut inspected_fox: ResMut<InspectedFox>,
+    mut fox_mouseup_events: EventReader<FoxMouseupEvent>,
+) {
+    for ev in fox_mouseup_events.read() {
+        inspected_fox.pinned = Some(ev.0);
+    }
+}
+
+/// Shows everything about the hovered fox, or the pinned one if none is hovered. Hidden while
+/// there's neither.
+#[derive(Component)]
+pub(super) struct FoxCard;
+impl FoxCard {
+    const TITLE_FONT_SIZE: f32 = 28.;
+    const FONT_SIZE: f32 = 20.;
+
+    pub(super) fn spawn(root: &mut ChildBuilder<'_>) {
+        root.spawn((
+            Self,
+            Node {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                right: Val::Px(10.),
+                bottom: Val::Px(10.),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.)),
+                row_gap: Val::Px(4.),
+                ..default()
+            },
+            BackgroundColor(Color::Srgba(STONE_800).with_alpha(0.9)),
+        ));
+    }
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(
+        mut commands: Commands,
+        mut inspected_fox: ResMut<InspectedFox>,
+        fox_card: Single<(Entity, &mut Node), With<Self>>,
+        hovered_foxes_q: Query<Entity, (With<Fox>, With<Hovered>)>,
+        foxes_q: Query<Ref<Fox>>,
+    ) {
+        // Released foxes are gone for good
+        if inspected_fox
+            .pinned
+            .is_some_and(|pinned| !foxes_q.contains(pinned))
+        {
+            inspected_fox.pinned = None;
+        }
+        let target = hovered_foxes_q.iter().next().or(inspected_fox.pinned);
+        let fox = target.and_then(|target| foxes_q.get(target).ok());
+        let pinned = target.is_some() && target == inspected_fox.pinned;
+        if target == inspected_fox.shown
+            && pinned == inspected_fox.shown_pinned
+            && !fox.as_ref().is_some_and(DetectChanges::is_changed)
+        {
+            return;
+        }
+        inspected_fox.shown = target;
+        inspected_fox.shown_pinned = pinned;
+
+        let (fox_card, mut node) = fox_card.into_inner();
+        let mut fox_card = commands.entity(fox_card);
+        fox_card.despawn_descendants();
+        let Some(fox) = fox else {
+            node.display = Display::None;
+            return;
+        };
+        node.display = Display::Flex;
+        fox_card.with_children(|fox_card| {
+            fox_card
+                .spawn(Node {
+                    column_gap: Val::Px(10.),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::SpaceBetween,
+                    ..default()
+                })
+                .with_children(|title_row| {
+                    title_row.spawn((
+                        Text::new(fox.name().to_string()),
+                        TextFont::from_font_size(Self::TITLE_FONT_SIZE),
+                    ));
+                    if pinned {
+                        UnpinButton::spawn(title_row);
+                    }
+                });
+            let now = unix_seconds();
+            let mut lines = vec![
+                format!("Species: {}", fox.species()),
+                format!("Age: {}", fox.age()),
+                format!("Happiness: {}", fox.happiness()),
+                format!(
+                    "Favorite Activity: {} ({})",
+                    fox.favorite_activity_type(),
+                    if fox.is_satisfied() {
+                        "satisfied"
+                    } else {
+                        "not satisfied"
+                    }
+                ),
+            ];
+            for problem_slot in ProblemSlot::ALL {
+                lines.push(format!(
+                    "{problem_slot} Problem: {}",
+                    fox.problem_state(problem_slot, now)
+                ));
+            }
+            lines.push(format!("Income: ${} per payout", fox.income()));
+            for line in lines {
+                fox_card.spawn((Text::new(line), TextFont::from_font_size(Self::FONT_SIZE)));
+            }
+        });
+    }
+}
+#[derive(Component)]
+struct UnpinButton;
+impl UnpinButton {
+    const FONT_SIZE: f32 = 20.;
+
+    fn spawn(title_row: &mut ChildBuilder<'_>) {
+        title_row.spawn((
+            Self,
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(6.), Val::Px(2.)),
+                ..default()
+            },
+            BackgroundColor(Color::from(STONE_600)),
+            Text::new("Close"),
+            TextFont::from_font_size(Self::FONT_SIZE),
+        ));
+    }
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(
+        mut inspected_fox: ResMut<InspectedFox>,
+        unpin_button_interaction_q: Query<&Interaction, (Changed<Interaction>, With<Self>)>,
+    ) {
+        if unpin_button_interaction_q
+            .iter()
+            .any(|interaction| *interaction == Interaction::Pressed)
+        {
+            inspected_fox.pinned = None;
+        }
+    }
+}
+
+pub(super) struct InspectionPlugin;
+impl Plugin for InspectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(InspectedFox::default()).add_systems(
+            Update,
+            (
+                (pin_fox.after(ClickableSet), UnpinButton::system),
+                FoxCard::system,
+            )
+                .chain()
+                .run_if(in_state(AppState::Merge)),
+        );
+    }
+}
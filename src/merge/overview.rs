@@ -0,0 +1,414 @@
+use std::{cmp::Ordering, fmt::Display};
+
+use bevy::{
+    app::{App, Plugin, PostUpdate, Update},
+    color::{
+        palettes::tailwind::{LIME_300, LIME_500, STONE_600, STONE_800},
+        Alpha, Color, Srgba,
+    },
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::EventReader,
+        query::{Changed, With, Without},
+        schedule::{common_conditions::resource_changed, Condition, IntoSystemConfigs},
+        system::{Commands, Query, Res, ResMut, Resource, Single},
+    },
+    hierarchy::{BuildChildren, ChildBuild, ChildBuilder, DespawnRecursiveExt, Parent},
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    sprite::Sprite,
+    state::condition::in_state,
+    text::TextFont,
+    ui::{
+        widget::{Button, Text},
+        AlignItems, BackgroundColor, Display as NodeDisplay, FlexDirection, Interaction, Node,
+        Overflow, PositionType, RelativeCursorPosition, ScrollPosition, UiRect, Val,
+    },
+    utils::default,
+};
+
+use crate::{app_state::AppState, fox::Fox};
+
+use super::{fox_lot::FoxSanctuary, fox_sanctuaries_changed, foxes_changed};
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+enum FoxSort {
+    #[default]
+    Name,
+    Species,
+    Age,
+    Income,
+}
+impl FoxSort {
+    const ALL: [Self; 4] = [Self::Name, Self::Species, Self::Age, Self::Income];
+
+    /// Highest income first, and everything else in ascending order.
+    fn compare(self, a: &Fox, b: &Fox) -> Ordering {
+        match self {
+            Self::Name => a.name().cmp(b.name()),
+            Self::Species => a.species().to_string().cmp(&b.species().to_string()),
+            Self::Age => a.age().cmp(b.age()),
+            Self::Income => b.income().cmp(&a.income()),
+        }
+    }
+}
+impl Display for FoxSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Name => "Name",
+                Self::Species => "Species",
+                Self::Age => "Age",
+                Self::Income => "Income",
+            }
+        )
+    }
+}
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+enum FoxFilter {
+    #[default]
+    All,
+    UnfixedProblem,
+    UnsatisfiedActivity,
+}
+impl FoxFilter {
+    const ALL: [Self; 3] = [Self::All, Self::UnfixedProblem, Self::UnsatisfiedActivity];
+
+    const fn matches(self, fox: &Fox) -> bool {
+        match self {
+            Self::All => true,
+            Self::UnfixedProblem => !fox.is_rehabilitated(),
+            Self::UnsatisfiedActivity => !fox.is_satisfied(),
+        }
+    }
+}
+impl Display for FoxFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::All => "All",
+                Self::UnfixedProblem => "Unfixed Problem",
+                Self::UnsatisfiedActivity => "Unsatisfied",
+            }
+        )
+    }
+}
+/// How the [`OverviewPanel`] lists the foxes, and which one the player selected.
+#[derive(Resource, Default)]
+struct FoxOverview {
+    sort: FoxSort,
+    filter: FoxFilter,
+    /// The fox whose [`FoxSanctuary`] is highlighted.
+    selected: Option<Entity>,
+}
+
+const BUTTON_COLOR: Srgba = STONE_600;
+const ACTIVE_BUTTON_COLOR: Srgba = LIME_500;
+
+#[derive(Component)]
+pub(super) struct OverviewButton;
+impl OverviewButton {
+    const FONT_SIZE: f32 = 30.;
+
+    pub(super) fn spawn(top_container: &mut ChildBuilder<'_>) {
+        top_container.spawn((
+            Self,
+            Button,
+            Text::new("Foxes"),
+            TextFont::from_font_size(Self::FONT_SIZE),
+        ));
+    }
+}
+/// Lists every fox in every [`FoxSanctuary`]. Hidden until the [`OverviewButton`] is pressed.
+#[derive(Component)]
+pub(super) struct OverviewPanel;
+impl OverviewPanel {
+    const FONT_SIZE: f32 = 20.;
+
+    pub(super) fn spawn(top_container: &mut ChildBuilder<'_>) {
+        top_container
+            .spawn((
+                Self,
+                Node {
+                    display: NodeDisplay::None,
+                    position_type: PositionType::Absolute,
+                    top: Val::Percent(100.),
+                    left: Val::Px(10.),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(10.)),
+                    row_gap: Val::Px(8.),
+                    ..default()
+                },
+                BackgroundColor(Color::Srgba(STONE_800).with_alpha(0.9)),
+            ))
+            .with_children(|overview_panel| {
+                Self::spawn_control_row(overview_panel, "Sort by", |control_row| {
+                    for fox_sort in FoxSort::ALL {
+                        spawn_control_button(control_row, SortButton(fox_sort), fox_sort);
+                    }
+                });
+                Self::spawn_control_row(overview_panel, "Show", |control_row| {
+                    for fox_filter in FoxFilter::ALL {
+                        spawn_control_button(control_row, FilterButton(fox_filter), fox_filter);
+                    }
+                });
+                OverviewList::spawn(overview_panel);
+            });
+    }
+    fn spawn_control_row(
+        overview_panel: &mut ChildBuilder<'_>,
+        label: &str,
+        spawn_buttons: impl FnOnce(&mut ChildBuilder<'_>),
+    ) {
+        overview_panel
+            .spawn(Node {
+                column_gap: Val::Px(6.),
+                align_items: AlignItems::Center,
+                ..default()
+            })
+            .with_children(|control_row| {
+                control_row.spawn((Text::new(label), TextFont::from_font_size(Self::FONT_SIZE)));
+                spawn_buttons(control_row);
+            });
+    }
+}
+fn spawn_control_button(
+    control_row: &mut ChildBuilder<'_>,
+    button: impl Component,
+    label: impl Display,
+) {
+    control_row.spawn((
+        button,
+        Button,
+        Node {
+            padding: UiRect::axes(Val::Px(6.), Val::Px(2.)),
+            ..default()
+        },
+        BackgroundColor(Color::from(BUTTON_COLOR)),
+        Text::new(label.to_string()),
+        TextFont::from_font_size(OverviewPanel::FONT_SIZE),
+    ));
+}
+#[derive(Component)]
+struct SortButton(FoxSort);
+#[derive(Component)]
+struct FilterButton(FoxFilter);
+/// Applies the pressed [`SortButton`] or [`FilterButton`].
+#[allow(clippy::needless_pass_by_value)]
+fn press_control_buttons(
+    mut fox_overview: ResMut<FoxOverview>,
+    sort_buttons_q: Query<(&Interaction, &SortButton), Changed<Interaction>>,
+    filter_buttons_q: Query<(&Interaction, &FilterButton), Changed<Interaction>>,
+) {
+    for (interaction, sort_button) in &sort_buttons_q {
+        if *interaction == Interaction::Pressed {
+            fox_overview.sort = sort_button.0;
+        }
+    }
+    for (interaction, filter_button) in &filter_buttons_q {
+        if *interaction == Interaction::Pressed {
+            fox_overview.filter = filter_button.0;
+        }
+    }
+}
+#[allow(clippy::needless_pass_by_value)]
+fn highlight_control_buttons(
+    fox_overview: Res<FoxOverview>,
+    mut sort_buttons_q: Query<(&SortButton, &mut BackgroundColor), Without<FilterButton>>,
+    mut filter_buttons_q: Query<(&FilterButton, &mut BackgroundColor)>,
+) {
+    let color = |active: bool| {
+        Color::from(if active {
+            ACTIVE_BUTTON_COLOR
+        } else {
+            BUTTON_COLOR
+        })
+    };
+    for (sort_button, mut background_color) in &mut sort_buttons_q {
+        background_color.0 = color(fox_overview.sort == sort_button.0);
+    }
+    for (filter_button, mut background_color) in &mut filter_buttons_q {
+        background_color.0 = color(fox_overview.filter == filter_button.0);
+    }
+}
+/// The scrollable part of the [`OverviewPanel`], one [`OverviewRow`] per listed fox. It's never
+/// respawned, so it keeps its scroll position while the rows are rebuilt.
+#[derive(Component)]
+struct OverviewList;
+impl OverviewList {
+    const MAX_HEIGHT: f32 = 400.;
+    /// How far one notch of the mouse wheel scrolls.
+    const LINE_HEIGHT: f32 = 24.;
+
+    fn spawn(overview_panel: &mut ChildBuilder<'_>) {
+        overview_panel.spawn((
+            Self,
+            Node {
+                max_height: Val::Px(Self::MAX_HEIGHT),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(2.),
+                overflow: Overflow::scroll_y(),
+                ..default()
+            },
+            RelativeCursorPosition::default(),
+        ));
+    }
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(
+        mut commands: Commands,
+        fox_overview: Res<FoxOverview>,
+        overview_list: Single<Entity, With<Self>>,
+        foxes_q: Query<(Entity, &Fox)>,
+    ) {
+        let mut foxes: Vec<_> = foxes_q
+            .iter()
+            .filter(|(_, fox)| fox_overview.filter.matches(fox))
+            .collect();
+        foxes.sort_by(|(_, a), (_, b)| fox_overview.sort.compare(a, b));
+
+        let mut overview_list = commands.entity(*overview_list);
+        overview_list.despawn_descendants();
+        overview_list.with_children(|overview_list| {
+            if foxes.is_empty() {
+                overview_list.spawn((
+                    Text::new("No foxes to show"),
+                    TextFont::from_font_size(OverviewPanel::FONT_SIZE),
+                ));
+            }
+            for (fox_entity, fox) in foxes {
+                OverviewRow::spawn(
+                    overview_list,
+                    fox_entity,
+                    fox,
+                    fox_overview.selected == Some(fox_entity),
+                );
+            }
+        });
+    }
+    #[allow(clippy::needless_pass_by_value)]
+    fn scroll(
+        mut mouse_wheel_events: EventReader<MouseWheel>,
+        mut overview_lists_q: Query<(&mut ScrollPosition, &RelativeCursorPosition), With<Self>>,
+    ) {
+        for ev in mouse_wheel_events.read() {
+            let dy = match ev.unit {
+                MouseScrollUnit::Line => ev.y * Self::LINE_HEIGHT,
+                MouseScrollUnit::Pixel => ev.y,
+            };
+            for (mut scroll_position, relative_cursor_position) in &mut overview_lists_q {
+                if relative_cursor_position.mouse_over() {
+                    // The layout clamps this to the list's content
+                    scroll_position.offset_y -= dy;
+                }
+            }
+        }
+    }
+}
+/// One fox in the [`OverviewList`]. Pressing it selects the fox, or deselects it if it already
+/// was.
+#[derive(Component)]
+struct OverviewRow(Entity);
+impl OverviewRow {
+    fn spawn(overview_list: &mut ChildBuilder<'_>, fox_entity: Entity, fox: &Fox, selected: bool) {
+        overview_list.spawn((
+            Self(fox_entity),
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(6.), Val::Px(2.)),
+                ..default()
+            },
+            BackgroundColor(if selected {
+                Color::from(ACTIVE_BUTTON_COLOR)
+            } else {
+                Color::NONE
+            }),
+            Text::new(format!(
+                "{} ({}), {}, ${}",
+                fox.name(),
+                fox.species(),
+                fox.age(),
+                fox.income()
+            )),
+            TextFont::from_font_size(OverviewPanel::FONT_SIZE),
+        ));
+    }
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(
+        mut fox_overview: ResMut<FoxOverview>,
+        overview_rows_q: Query<(&Interaction, &Self), Changed<Interaction>>,
+    ) {
+        for (interaction, overview_row) in &overview_rows_q {
+            if *interaction == Interaction::Pressed {
+                fox_overview.selected = if fox_overview.selected == Some(overview_row.0) {
+                    None
+                } else {
+                    Some(overview_row.0)
+                };
+            }
+        }
+    }
+}
+/// Tints the [`FoxSanctuary`] the selected fox lives in, following it if it moves.
+#[allow(clippy::needless_pass_by_value)]
+fn highlight_selected_sanctuary(
+    mut fox_overview: ResMut<FoxOverview>,
+    foxes_q: Query<&Parent, With<Fox>>,
+    mut fox_sanctuaries_q: Query<(Entity, &mut Sprite), With<FoxSanctuary>>,
+) {
+    let selected_sanctuary = match fox_overview.selected {
+        Some(selected) => {
+            let Ok(parent) = foxes_q.get(selected) else {
+                // Released foxes are gone for good
+                fox_overview.selected = None;
+                return;
+            };
+            Some(parent.get())
+        }
+        None => None,
+    };
+    for (entity, mut sprite) in &mut fox_sanctuaries_q {
+        let color = if selected_sanctuary == Some(entity) {
+            Color::from(LIME_300)
+        } else {
+            Color::WHITE
+        };
+        // Only write when it differs, so the sprites aren't marked changed every frame
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+}
+
+pub(super) struct OverviewPlugin;
+impl Plugin for OverviewPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(FoxOverview::default())
+            .add_systems(
+                Update,
+                (
+                    (
+                        press_control_buttons,
+                        highlight_control_buttons.run_if(resource_changed::<FoxOverview>),
+                    )
+                        .chain(),
+                    OverviewRow::system,
+                    OverviewList::scroll,
+                    highlight_selected_sanctuary,
+                )
+                    .run_if(in_state(AppState::Merge)),
+            )
+            // After every Update system, so foxes despawned by one are already gone
+            .add_systems(
+                PostUpdate,
+                OverviewList::system.run_if(
+                    resource_changed::<FoxOverview>
+                        .or(fox_sanctuaries_changed)
+                        .or(foxes_changed),
+                ),
+            );
+    }
+}
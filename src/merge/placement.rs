@@ -0,0 +1,310 @@
+use bevy::{
+    app::{App, Plugin, PostUpdate, Update},
+    asset::AssetServer,
+    color::{
+        palettes::tailwind::{STONE_600, STONE_800},
+        Alpha, Color,
+    },
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::{Changed, With},
+        schedule::{
+            common_conditions::{not, resource_changed},
+            Condition, IntoSystemConfigs,
+        },
+        system::{Commands, Query, Res, ResMut, Resource, Single, SystemParam},
+    },
+    hierarchy::{BuildChildren, ChildBuild, ChildBuilder, DespawnRecursiveExt},
+    input::{common_conditions::input_just_released, mouse::MouseButton},
+    math::Vec2,
+    sprite::Sprite,
+    state::condition::in_state,
+    text::TextFont,
+    transform::components::Transform,
+    ui::{
+        widget::{Button, ImageNode, Text},
+        AlignItems, BackgroundColor, Display, FlexDirection, FlexWrap, Interaction, Node,
+        PositionType, UiRect, Val,
+    },
+    utils::default,
+};
+
+use crate::{
+    app_state::AppState,
+    clickable::{ClickableSet, Hovered},
+    fox::Fox,
+    unix_seconds, FollowMouse,
+};
+
+use super::{
+    fox_lot::FoxSanctuary,
+    holding_pen::{place_foxes, HoldingPen},
+    FoxStorageInfo,
+};
+
+/// Foxes caught in the last searches, waiting for the player to put each in a
+/// [`FoxSanctuary`].
+#[derive(Resource, Default)]
+pub(crate) struct PlacementTray {
+    pub(crate) foxes: Vec<Fox>,
+    /// The index of the fox being dragged out of the tray.
+    dragging: Option<usize>,
+}
+impl PlacementTray {
+    pub(crate) const fn is_dragging(&self) -> bool {
+        self.dragging.is_some()
+    }
+}
+fn dragging_tray_fox(placement_tray: Res<PlacementTray>) -> bool {
+    placement_tray.is_dragging()
+}
+
+/// Shows the [`PlacementTray`]. Hidden while it's empty.
+#[derive(Component)]
+pub(super) struct PlacementTrayPanel;
+impl PlacementTrayPanel {
+    const FONT_SIZE: f32 = 24.;
+
+    pub(super) fn spawn(root: &mut ChildBuilder<'_>) {
+        root.spawn((
+            Self,
+            Node {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                left: Val::Px(10.),
+                bottom: Val::Px(10.),
+                max_width: Val::Percent(40.),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.)),
+                row_gap: Val::Px(8.),
+                ..default()
+            },
+            BackgroundColor(Color::Srgba(STONE_800).with_alpha(0.9)),
+        ));
+    }
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        placement_tray: Res<PlacementTray>,
+        placement_tray_panel: Single<(Entity, &mut Node), With<Self>>,
+    ) {
+        let (placement_tray_panel, mut node) = placement_tray_panel.into_inner();
+        node.display = if placement_tray.foxes.is_empty() {
+            Display::None
+        } else {
+            Display::Flex
+        };
+        let mut placement_tray_panel = commands.entity(placement_tray_panel);
+        placement_tray_panel.despawn_descendants();
+        placement_tray_panel.with_children(|placement_tray_panel| {
+            placement_tray_panel.spawn((
+                Text::new("Drag each caught fox to a sanctuary with room"),
+                TextFont::from_font_size(Self::FONT_SIZE),
+            ));
+            placement_tray_panel
+                .spawn(Node {
+                    flex_wrap: FlexWrap::Wrap,
+                    column_gap: Val::Px(8.),
+                    row_gap: Val::Px(8.),
+                    ..default()
+                })
+                .with_children(|tray_foxes| {
+                    for (fox_index, fox) in placement_tray.foxes.iter().enumerate() {
+                        // The fox being dragged leaves a gap behind
+                        let dragging = placement_tray.dragging == Some(fox_index);
+                        TrayFoxButton::spawn(tray_foxes, &asset_server, fox_index, fox, dragging);
+                    }
+                });
+            AutoPlaceButton::spawn(placement_tray_panel);
+        });
+    }
+}
+#[derive(Component)]
+struct TrayFoxButton(usize);
+impl TrayFoxButton {
+    const SIZE: f32 = 48.;
+    const FONT_SIZE: f32 = 16.;
+
+    fn spawn(
+        tray_foxes: &mut ChildBuilder<'_>,
+        asset_server: &Res<AssetServer>,
+        fox_index: usize,
+        fox: &Fox,
+        dragging: bool,
+    ) {
+        let species_data = fox.species().data();
+        tray_foxes
+            .spawn((
+                Self(fox_index),
+                Button,
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+            ))
+            .with_children(|tray_fox_button| {
+                tray_fox_button.spawn((
+                    ImageNode {
+                        image: asset_server.load(&species_data.sprite),
+                        color: Color::WHITE.with_alpha(if dragging { 0.25 } else { 1. }),
+                        ..default()
+                    },
+                    Node {
+                        width: Val::Px(Self::SIZE),
+                        height: Val::Px(Self::SIZE),
+                        ..default()
+                    },
+                ));
+                tray_fox_button.spawn((
+                    Text::new(fox.name().to_string()),
+                    TextFont::from_font_size(Self::FONT_SIZE),
+                ));
+            });
+    }
+    /// Picks the pressed fox up, to be dropped on a sanctuary by [`drop_tray_fox`].
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        mut placement_tray: ResMut<PlacementTray>,
+        tray_fox_buttons_q: Query<(&Interaction, &Self), Changed<Interaction>>,
+    ) {
+        for (interaction, tray_fox_button) in &tray_fox_buttons_q {
+            if *interaction != Interaction::Pressed || placement_tray.is_dragging() {
+                continue;
+            }
+            let Some(fox) = placement_tray.foxes.get(tray_fox_button.0) else {
+                continue;
+            };
+            let species_data = fox.species().data();
+            commands.spawn((
+                DraggedTrayFox,
+                Sprite {
+                    image: asset_server.load(&species_data.sprite),
+                    custom_size: Some(Vec2::splat(Self::SIZE)),
+                    ..default()
+                },
+                Transform::from_xyz(0., 0., 10.),
+                FollowMouse {
+                    parent: None,
+                    previous_transform: Transform::default(),
+                },
+            ));
+            placement_tray.dragging = Some(tray_fox_button.0);
+        }
+    }
+}
+/// The sprite of the fox being dragged out of the [`PlacementTray`].
+#[derive(Component)]
+struct DraggedTrayFox;
+#[derive(SystemParam)]
+struct DropTrayFoxResources<'w> {
+    placement_tray: ResMut<'w, PlacementTray>,
+    fox_storage_info: ResMut<'w, FoxStorageInfo>,
+}
+/// Puts the dragged fox in the sanctuary it's dropped on if that has room, and otherwise back in
+/// the tray.
+#[allow(clippy::needless_pass_by_value)]
+fn drop_tray_fox(
+    mut commands: Commands,
+    mut resources: DropTrayFoxResources,
+    dragged_tray_foxes_q: Query<Entity, With<DraggedTrayFox>>,
+    mut hovered_fox_sanctuaries_q: Query<(Entity, &mut FoxSanctuary), With<Hovered>>,
+) {
+    for dragged_tray_fox in &dragged_tray_foxes_q {
+        commands.entity(dragged_tray_fox).despawn_recursive();
+    }
+    let Some(fox_index) = resources.placement_tray.dragging.take() else {
+        return;
+    };
+    if let Some((entity, mut fox_sanctuary)) = hovered_fox_sanctuaries_q
+        .iter_mut()
+        .find(|(_, fox_sanctuary)| fox_sanctuary.has_room())
+    {
+        let fox = resources.placement_tray.foxes.remove(fox_index);
+        fox_sanctuary.push_fox(&mut commands, entity, fox);
+        resources.fox_storage_info.total_foxes += 1;
+    }
+}
+#[derive(Component)]
+struct AutoPlaceButton;
+#[derive(SystemParam)]
+struct AutoPlaceResources<'w, 's> {
+    commands: Commands<'w, 's>,
+    placement_tray: ResMut<'w, PlacementTray>,
+    holding_pen: ResMut<'w, HoldingPen>,
+    fox_storage_info: ResMut<'w, FoxStorageInfo>,
+}
+impl AutoPlaceButton {
+    const FONT_SIZE: f32 = 20.;
+
+    fn spawn(placement_tray_panel: &mut ChildBuilder<'_>) {
+        placement_tray_panel.spawn((
+            Self,
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(6.), Val::Px(2.)),
+                ..default()
+            },
+            BackgroundColor(Color::from(STONE_600)),
+            Text::new("Auto place"),
+            TextFont::from_font_size(Self::FONT_SIZE),
+        ));
+    }
+    /// Fills the highest level sanctuaries first, holding any foxes that don't fit in the
+    /// [`HoldingPen`].
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(
+        mut resources: AutoPlaceResources,
+        auto_place_button_interaction_q: Query<&Interaction, (Changed<Interaction>, With<Self>)>,
+        mut fox_sanctuaries_q: Query<(Entity, &mut FoxSanctuary)>,
+    ) {
+        if !auto_place_button_interaction_q
+            .iter()
+            .any(|interaction| *interaction == Interaction::Pressed)
+        {
+            return;
+        }
+        let foxes = std::mem::take(&mut resources.placement_tray.foxes);
+        let now = unix_seconds();
+        for fox in place_foxes(
+            &mut resources.commands,
+            &mut resources.fox_storage_info,
+            &mut fox_sanctuaries_q,
+            foxes,
+        ) {
+            resources.holding_pen.hold(fox, now);
+        }
+    }
+}
+
+pub(super) struct PlacementPlugin;
+impl Plugin for PlacementPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(PlacementTray::default())
+            .add_systems(
+                Update,
+                (
+                    (
+                        TrayFoxButton::system.before(ClickableSet),
+                        AutoPlaceButton::system.run_if(not(dragging_tray_fox)),
+                    )
+                        .run_if(in_state(AppState::Merge)),
+                    PlacementTrayPanel::system.run_if(resource_changed::<PlacementTray>),
+                ),
+            )
+            // After every Update system, so none of them sees the mouseup on the sanctuary the fox
+            // is dropped on
+            .add_systems(
+                PostUpdate,
+                drop_tray_fox.run_if(
+                    in_state(AppState::Merge)
+                        .and(input_just_released(MouseButton::Left))
+                        .and(dragging_tray_fox),
+                ),
+            );
+    }
+}
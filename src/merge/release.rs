@@ -0,0 +1,133 @@
+use std::fmt::Display;
+
+use bevy::{
+    app::{App, Plugin, Update},
+    color::{palettes::tailwind::STONE_800, Alpha, Color},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::With,
+        schedule::{common_conditions::resource_changed, IntoSystemConfigs},
+        system::{Commands, Res, Resource, Single},
+    },
+    hierarchy::{BuildChildren, ChildBuild, ChildBuilder, DespawnRecursiveExt},
+    text::TextFont,
+    ui::{
+        widget::{Button, Text},
+        BackgroundColor, Display as NodeDisplay, FlexDirection, Node, PositionType, UiRect, Val,
+    },
+    utils::default,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    fox::{species::FoxSpecies, Fox, Name},
+    time_of_day_string, unix_seconds, Money,
+};
+
+/// A fox that was released back to the wild.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ReleasedFox {
+    name: Name,
+    species: FoxSpecies,
+    reward: Money,
+    /// Seconds since the Unix epoch.
+    timestamp: u64,
+}
+impl ReleasedFox {
+    pub(crate) fn new(fox: &Fox, reward: Money) -> Self {
+        Self {
+            name: fox.name().clone(),
+            species: fox.species(),
+            reward,
+            timestamp: unix_seconds(),
+        }
+    }
+}
+impl Display for ReleasedFox {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}  {} ({})  +${}",
+            time_of_day_string(self.timestamp),
+            self.name,
+            self.species,
+            self.reward
+        )
+    }
+}
+/// Every [`ReleasedFox`], oldest first.
+#[derive(Resource, Default)]
+pub(crate) struct ReleasedFoxes(pub(crate) Vec<ReleasedFox>);
+
+#[derive(Component)]
+pub(super) struct ReleasedFoxesButton;
+impl ReleasedFoxesButton {
+    const FONT_SIZE: f32 = 30.;
+
+    pub(super) fn spawn(top_container: &mut ChildBuilder<'_>) {
+        top_container.spawn((
+            Self,
+            Button,
+            Text::new("Released"),
+            TextFont::from_font_size(Self::FONT_SIZE),
+        ));
+    }
+}
+/// Lists the [`ReleasedFoxes`], newest first. Hidden until the [`ReleasedFoxesButton`] is
+/// pressed.
+#[derive(Component)]
+pub(super) struct ReleasedFoxesPanel;
+impl ReleasedFoxesPanel {
+    const FONT_SIZE: f32 = 24.;
+
+    pub(super) fn spawn(top_container: &mut ChildBuilder<'_>) {
+        top_container.spawn((
+            Self,
+            Node {
+                display: NodeDisplay::None,
+                position_type: PositionType::Absolute,
+                top: Val::Percent(100.),
+                right: Val::Px(10.),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.)),
+                row_gap: Val::Px(4.),
+                ..default()
+            },
+            BackgroundColor(Color::Srgba(STONE_800).with_alpha(0.9)),
+        ));
+    }
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(
+        mut commands: Commands,
+        released_foxes: Res<ReleasedFoxes>,
+        released_foxes_panel: Single<Entity, With<Self>>,
+    ) {
+        let mut released_foxes_panel = commands.entity(*released_foxes_panel);
+        released_foxes_panel.despawn_descendants();
+        released_foxes_panel.with_children(|released_foxes_panel| {
+            if released_foxes.0.is_empty() {
+                released_foxes_panel.spawn((
+                    Text::new("No foxes released yet"),
+                    TextFont::from_font_size(Self::FONT_SIZE),
+                ));
+            }
+            for released_fox in released_foxes.0.iter().rev() {
+                released_foxes_panel.spawn((
+                    Text::new(released_fox.to_string()),
+                    TextFont::from_font_size(Self::FONT_SIZE),
+                ));
+            }
+        });
+    }
+}
+
+pub(super) struct ReleasePlugin;
+impl Plugin for ReleasePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ReleasedFoxes::default()).add_systems(
+            Update,
+            ReleasedFoxesPanel::system.run_if(resource_changed::<ReleasedFoxes>),
+        );
+    }
+}
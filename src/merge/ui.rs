@@ -1,34 +1,43 @@
 use bevy::{
     app::{App, Plugin, Startup, Update},
     asset::AssetServer,
-    color::{palettes::tailwind::GRAY_300, Color},
+    color::{palettes::tailwind::STONE_800, Alpha, Color},
     ecs::{
         component::Component,
+        entity::Entity,
         query::{Changed, With},
         schedule::{common_conditions::resource_changed, IntoSystemConfigs},
-        system::{Commands, Query, Res, ResMut},
+        system::{Commands, Query, Res, ResMut, Single},
     },
-    hierarchy::{BuildChildren, ChildBuild, ChildBuilder},
-    math::{Vec2, Vec3},
-    sprite::Sprite,
+    hierarchy::{BuildChildren, ChildBuild, ChildBuilder, DespawnRecursiveExt},
     state::{condition::in_state, state::NextState},
-    text::{JustifyText, Text2d, TextFont, TextLayout},
-    transform::components::Transform,
+    text::TextFont,
     ui::{
         widget::{Button, Text},
-        AlignItems, AlignSelf, BackgroundColor, FlexDirection, Interaction, JustifyContent,
-        JustifySelf, Node, UiRect, Val,
+        AlignItems, AlignSelf, BackgroundColor, Display, FlexDirection, Interaction,
+        JustifyContent, JustifySelf, Node, PositionType, UiRect, Val,
     },
     utils::default,
 };
 
 use crate::{
     app_state::{AppState, Merge},
-    search::{cell::LEVEL_CELLS, Level},
-    ui::{CoinUI, MoneyContainer, RootTrait},
+    money::{Ledger, TransactionReason, Wallet},
+    search::level::CurrentLevel,
+    ui::{MoneyContainer, RootTrait},
+    Money,
 };
 
-use super::{fox_lot::FoxLotPrice, FoxStorageInfo};
+use super::{
+    care::{CareButton, CarePanel},
+    enrichment::{EnrichmentButton, EnrichmentPanel},
+    holding_pen::HoldingPenPanel,
+    inspection::FoxCard,
+    overview::{OverviewButton, OverviewPanel},
+    placement::PlacementTrayPanel,
+    release::{ReleasedFoxesButton, ReleasedFoxesPanel},
+    FoxStorageInfo,
+};
 
 #[derive(Component)]
 struct Root;
@@ -49,6 +58,8 @@ impl RootTrait for Root {
             .with_children(|root| {
                 TopContainer::spawn(root, asset_server);
                 SearchButton::spawn(root);
+                PlacementTrayPanel::spawn(root);
+                FoxCard::spawn(root);
             });
     }
 }
@@ -69,6 +80,96 @@ impl TopContainer {
         ))
         .with_children(|top_container| {
             MoneyContainer::spawn(top_container, asset_server);
+            HoldingPenPanel::spawn(top_container);
+            LedgerButton::spawn(top_container);
+            LedgerPanel::spawn(top_container);
+            CareButton::spawn(top_container);
+            CarePanel::spawn(top_container);
+            EnrichmentButton::spawn(top_container);
+            EnrichmentPanel::spawn(top_container);
+            ReleasedFoxesButton::spawn(top_container);
+            ReleasedFoxesPanel::spawn(top_container);
+            OverviewButton::spawn(top_container);
+            OverviewPanel::spawn(top_container);
+        });
+    }
+}
+#[derive(Component)]
+struct LedgerButton;
+impl LedgerButton {
+    const FONT_SIZE: f32 = 30.;
+
+    fn spawn(top_container: &mut ChildBuilder<'_>) {
+        top_container.spawn((
+            Self,
+            Button,
+            Text::new("Ledger"),
+            TextFont::from_font_size(Self::FONT_SIZE),
+        ));
+    }
+}
+/// Shows or hides every `P` panel whenever a `B` button is pressed.
+#[allow(clippy::needless_pass_by_value)]
+fn toggle_panel<B: Component, P: Component>(
+    button_interaction_q: Query<&Interaction, (Changed<Interaction>, With<B>)>,
+    mut panels_q: Query<&mut Node, With<P>>,
+) {
+    if !button_interaction_q
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        return;
+    }
+    for mut panel_node in &mut panels_q {
+        panel_node.display = match panel_node.display {
+            Display::None => Display::Flex,
+            _ => Display::None,
+        };
+    }
+}
+/// Lists the [`Ledger`], newest first. Hidden until the [`LedgerButton`] is pressed.
+#[derive(Component)]
+struct LedgerPanel;
+impl LedgerPanel {
+    const FONT_SIZE: f32 = 24.;
+
+    fn spawn(top_container: &mut ChildBuilder<'_>) {
+        top_container.spawn((
+            Self,
+            Node {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                top: Val::Percent(100.),
+                left: Val::Px(10.),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.)),
+                row_gap: Val::Px(4.),
+                ..default()
+            },
+            BackgroundColor(Color::Srgba(STONE_800).with_alpha(0.9)),
+        ));
+    }
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(
+        mut commands: Commands,
+        ledger: Res<Ledger>,
+        ledger_panel: Single<Entity, With<Self>>,
+    ) {
+        let mut ledger_panel = commands.entity(*ledger_panel);
+        ledger_panel.despawn_descendants();
+        ledger_panel.with_children(|ledger_panel| {
+            if ledger.0.is_empty() {
+                ledger_panel.spawn((
+                    Text::new("No transactions yet"),
+                    TextFont::from_font_size(Self::FONT_SIZE),
+                ));
+            }
+            for money_transaction in ledger.0.iter().rev() {
+                ledger_panel.spawn((
+                    Text::new(money_transaction.to_string()),
+                    TextFont::from_font_size(Self::FONT_SIZE),
+                ));
+            }
         });
     }
 }
@@ -92,7 +193,8 @@ impl SearchButton {
     #[allow(clippy::needless_pass_by_value)]
     fn system(
         mut next_app_state: ResMut<NextState<AppState>>,
-        level: Res<Level>,
+        mut wallet: Wallet,
+        current_level: CurrentLevel,
         fox_storage_info: Res<FoxStorageInfo>,
         search_button_interaction_q: Query<&Interaction, (Changed<Interaction>, With<Self>)>,
     ) {
@@ -102,10 +204,16 @@ impl SearchButton {
 
         let search_button_interaction = search_button_interaction_q.single();
         if *search_button_interaction == Interaction::Pressed {
-            // Check fox sanctuary capacity
-            let total_foxes = &LEVEL_CELLS[level.0].1;
-            if fox_storage_info.remaining_capacity() >= total_foxes.0 {
-                next_app_state.set(AppState::Search);
+            // Check fox sanctuary capacity and pay the entry fee, if the level has one
+            if let Some(level_data) = current_level.get() {
+                if fox_storage_info.remaining_capacity() >= level_data.total_foxes.0
+                    && (level_data.entry_fee == Money::ZERO
+                        || wallet
+                            .try_spend(&level_data.entry_fee, TransactionReason::EntryFee)
+                            .is_ok())
+                {
+                    next_app_state.set(AppState::Search);
+                }
             }
         }
     }
@@ -114,14 +222,37 @@ impl SearchButton {
 struct SearchButtonText;
 impl SearchButtonText {
     const FONT_SIZE: f32 = 50.;
+    const TEXT: &str = "Search";
+    const ALL_COMPLETE_TEXT: &str = "All Levels Complete: Search the Wilds";
 
     fn spawn(search_button: &mut ChildBuilder<'_>) {
         search_button.spawn((
             Self,
-            Text::new("Search"),
+            Text::new(Self::TEXT),
             TextFont::from_font_size(Self::FONT_SIZE),
         ));
     }
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(
+        current_level: CurrentLevel,
+        mut search_button_texts_q: Query<&mut Text, With<Self>>,
+    ) {
+        let mut text = if current_level.all_complete() {
+            Self::ALL_COMPLETE_TEXT.to_owned()
+        } else {
+            Self::TEXT.to_owned()
+        };
+        if let Some(level_data) = current_level.get() {
+            if level_data.entry_fee > Money::ZERO {
+                text = format!("{text} (${})", level_data.entry_fee);
+            }
+        }
+        for mut search_button_text in &mut search_button_texts_q {
+            if search_button_text.0 != text {
+                search_button_text.0.clone_from(&text);
+            }
+        }
+    }
 }
 
 pub(super) struct UIPlugin;
@@ -129,7 +260,19 @@ impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, startup).add_systems(
             Update,
-            SearchButton::system.run_if(in_state(AppState::Merge)),
+            (
+                (
+                    SearchButton::system,
+                    SearchButtonText::system,
+                    toggle_panel::<LedgerButton, LedgerPanel>,
+                    toggle_panel::<CareButton, CarePanel>,
+                    toggle_panel::<EnrichmentButton, EnrichmentPanel>,
+                    toggle_panel::<ReleasedFoxesButton, ReleasedFoxesPanel>,
+                    toggle_panel::<OverviewButton, OverviewPanel>,
+                )
+                    .run_if(in_state(AppState::Merge)),
+                LedgerPanel::system.run_if(resource_changed::<Ledger>),
+            ),
         );
     }
 }
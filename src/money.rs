@@ -1,299 +1,477 @@
 use std::{
-    cmp::Ordering,
+    collections::VecDeque,
     fmt::Display,
-    ops::{Add, AddAssign, Div, RemAssign, Sub, SubAssign},
+    ops::{AddAssign, SubAssign},
+    str::FromStr,
 };
 
-use bevy::ecs::system::Resource;
+use bevy::{
+    app::{App, Plugin, Update},
+    ecs::{
+        event::{Event, EventReader, EventWriter},
+        system::{ResMut, Resource, SystemParam},
+    },
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{time_of_day_string, unix_seconds};
 
-#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+/// An amount of money, stored as a whole number of cents so that it never rounds.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub(crate) struct Money {
-    dollars: Dollar,
-    cents: Cent,
+    cents: u64,
 }
 impl Money {
-    pub(crate) const ZERO: Self = Self {
-        dollars: Dollar::ZERO,
-        cents: Cent::ZERO,
-    };
+    pub(crate) const ZERO: Self = Self { cents: 0 };
+    const CENTS_PER_DOLLAR: u64 = 100;
 
+    /// # Panics
+    ///
+    /// If `cents` is 100 or more, or the amount doesn't fit.
     pub(crate) const fn new(dollars: u64, cents: u8) -> Self {
-        Self {
-            dollars: Dollar(dollars),
-            cents: Cent(cents),
+        assert!(
+            (cents as u64) < Self::CENTS_PER_DOLLAR,
+            "cents must be under 100"
+        );
+        match dollars.checked_mul(Self::CENTS_PER_DOLLAR) {
+            Some(dollar_cents) => match dollar_cents.checked_add(cents as u64) {
+                Some(cents) => Self { cents },
+                None => panic!("the amount doesn't fit"),
+            },
+            None => panic!("the amount doesn't fit"),
         }
     }
+    pub(crate) const fn from_cents(cents: u64) -> Self {
+        Self { cents }
+    }
+    const fn dollars(&self) -> u64 {
+        self.cents / Self::CENTS_PER_DOLLAR
+    }
     pub(crate) fn dollars_string(&self) -> String {
-        self.dollars.to_string()
+        self.dollars().to_string()
+    }
+    /// Formats large amounts compactly, e.g. `1.2K`, `3.45M` or `7.8B`, keeping three significant
+    /// digits. Amounts under a thousand are shown in full.
+    pub(crate) fn abbreviated(&self) -> String {
+        const SUFFIXES: [(u64, &str); 4] = [
+            (1_000_000_000_000, "T"),
+            (1_000_000_000, "B"),
+            (1_000_000, "M"),
+            (1_000, "K"),
+        ];
+        let dollars = self.dollars();
+        for (scale, suffix) in SUFFIXES {
+            if dollars >= scale {
+                let whole = dollars / scale;
+                let decimals = if whole >= 100 {
+                    0
+                } else if whole >= 10 {
+                    1
+                } else {
+                    2
+                };
+                // Integer division rounds down, so that e.g. 999,999 shows as 999K rather than 1000K
+                let fraction = (dollars % scale) * 10u64.pow(decimals) / scale;
+                if fraction == 0 {
+                    return format!("{whole}{suffix}");
+                }
+                let fraction = format!("{fraction:0width$}", width = decimals as usize);
+                return format!("{whole}.{}{suffix}", fraction.trim_end_matches('0'));
+            }
+        }
+        self.to_string()
+    }
+    /// The amount `fraction` of the way from `self` to `to`.
+    pub(crate) fn lerp(&self, to: &Self, fraction: f32) -> Self {
+        let fraction = f64::from(fraction.clamp(0., 1.));
+        if to >= self {
+            to.checked_sub(self)
+                .and_then(|difference| difference.checked_mul_rate(fraction))
+                .and_then(|step| self.checked_add(&step))
+        } else {
+            self.checked_sub(to)
+                .and_then(|difference| difference.checked_mul_rate(fraction))
+                .and_then(|step| self.checked_sub(&step))
+        }
+        .unwrap_or_else(|| to.clone())
     }
-}
-impl AddAssign for Money {
-    fn add_assign(&mut self, rhs: Self) {
-        *self += rhs.cents;
-        *self += rhs.dollars;
+    pub(crate) const fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        match self.cents.checked_add(rhs.cents) {
+            Some(cents) => Some(Self { cents }),
+            None => None,
+        }
     }
-}
-impl AddAssign<&Self> for Money {
-    fn add_assign(&mut self, rhs: &Self) {
-        *self += &rhs.cents;
-        *self += &rhs.dollars;
+    pub(crate) const fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        match self.cents.checked_sub(rhs.cents) {
+            Some(cents) => Some(Self { cents }),
+            None => None,
+        }
     }
-}
-impl AddAssign<Cent> for Money {
-    fn add_assign(&mut self, rhs: Cent) {
-        self.cents += rhs;
-        if self.cents > Cent(100) {
-            self.dollars += Dollar::from(&self.cents);
-            self.cents %= Cent(100);
+    pub(crate) const fn checked_mul(&self, rhs: u64) -> Option<Self> {
+        match self.cents.checked_mul(rhs) {
+            Some(cents) => Some(Self { cents }),
+            None => None,
         }
     }
-}
-impl AddAssign<&Cent> for Money {
-    fn add_assign(&mut self, rhs: &Cent) {
-        self.cents += rhs;
-        if self.cents > Cent(100) {
-            self.dollars += Dollar::from(&self.cents);
-            self.cents %= Cent(100);
+    pub(crate) const fn saturating_mul(&self, rhs: u64) -> Self {
+        Self {
+            cents: self.cents.saturating_mul(rhs),
         }
     }
-}
-impl AddAssign<Dollar> for Money {
-    fn add_assign(&mut self, rhs: Dollar) {
-        self.dollars += rhs;
+    /// Multiplies by a rate such as `1.5`, rounding to the nearest cent.
+    ///
+    /// Returns [`None`] if the rate is negative or not a number, or if the result doesn't fit.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub(crate) fn checked_mul_rate(&self, rate: f64) -> Option<Self> {
+        let cents = (self.cents as f64 * rate).round();
+        (cents >= 0. && cents < u64::MAX as f64).then_some(Self {
+            cents: cents as u64,
+        })
+    }
+    /// Spends `price` if there is enough money, and otherwise leaves the money untouched.
+    pub(crate) fn try_spend(&mut self, price: &Self) -> Result<(), InsufficientFunds> {
+        *self = self.checked_sub(price).ok_or_else(|| InsufficientFunds {
+            price: price.clone(),
+            available: self.clone(),
+        })?;
+        Ok(())
+    }
+}
+/// Adding saturates at the largest amount rather than overflowing.
+impl AddAssign for Money {
+    fn add_assign(&mut self, rhs: Self) {
+        *self += &rhs;
     }
 }
-impl AddAssign<&Dollar> for Money {
-    fn add_assign(&mut self, rhs: &Dollar) {
-        self.dollars += rhs;
+impl AddAssign<&Self> for Money {
+    fn add_assign(&mut self, rhs: &Self) {
+        self.cents = self.cents.saturating_add(rhs.cents);
     }
 }
+/// Subtracting stops at zero; use [`Money::try_spend`] for anything the player pays for.
 impl SubAssign for Money {
     fn sub_assign(&mut self, rhs: Self) {
-        if rhs.ge(self) {
-            self.dollars = Dollar(0);
-            self.cents = Cent(0);
-        } else if rhs.cents > self.cents {
-            self.dollars -= Dollar(1) + rhs.dollars;
-            self.cents += Cent(100) - rhs.cents;
-        } else {
-            self.dollars -= rhs.dollars;
-            self.cents -= rhs.cents;
-        }
+        *self -= &rhs;
     }
 }
 impl SubAssign<&Self> for Money {
     fn sub_assign(&mut self, rhs: &Self) {
-        if rhs.ge(self) {
-            self.dollars = Dollar(0);
-            self.cents = Cent(0);
-        } else if rhs.cents > self.cents {
-            self.dollars -= Dollar(1) + &rhs.dollars;
-            self.cents += Cent(100) - &rhs.cents;
-        } else {
-            self.dollars -= &rhs.dollars;
-            self.cents -= &rhs.cents;
-        }
-    }
-}
-impl PartialOrd for Money {
-    fn lt(&self, other: &Self) -> bool {
-        if self.dollars.lt(&other.dollars) {
-            true
-        } else if self.dollars.eq(&other.dollars) {
-            self.cents.lt(&other.cents)
-        } else {
-            false
-        }
-    }
-
-    fn le(&self, other: &Self) -> bool {
-        if self.dollars.lt(&other.dollars) {
-            true
-        } else if self.dollars.eq(&other.dollars) {
-            self.cents.le(&other.cents)
-        } else {
-            false
-        }
-    }
-
-    fn gt(&self, other: &Self) -> bool {
-        if self.dollars.gt(&other.dollars) {
-            true
-        } else if self.dollars.eq(&other.dollars) {
-            self.cents.gt(&other.cents)
-        } else {
-            false
-        }
-    }
-
-    fn ge(&self, other: &Self) -> bool {
-        if self.dollars.gt(&other.dollars) {
-            true
-        } else if self.dollars.eq(&other.dollars) {
-            self.cents.ge(&other.cents)
-        } else {
-            false
-        }
-    }
-
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        if self.lt(other) {
-            Some(Ordering::Less)
-        } else if self.eq(other) {
-            Some(Ordering::Equal)
-        } else {
-            Some(Ordering::Greater)
-        }
+        self.cents = self.cents.saturating_sub(rhs.cents);
     }
 }
 impl Default for Money {
     fn default() -> Self {
-        Self {
-            dollars: Dollar(90),
-            cents: Cent(0),
-        }
+        Self::new(90, 0)
     }
 }
 impl Display for Money {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{}.{}",
-            self.dollars,
-            if self.cents < Cent(10) {
-                format!("0{}", self.cents)
-            } else {
-                self.cents.to_string()
-            }
+            "{}.{:02}",
+            self.dollars(),
+            self.cents % Self::CENTS_PER_DOLLAR
         )
     }
 }
-impl From<Dollar> for Money {
-    fn from(value: Dollar) -> Self {
-        Self {
-            dollars: value,
-            cents: Cent(0),
-        }
-    }
-}
-impl From<Cent> for Money {
-    fn from(value: Cent) -> Self {
-        Self {
-            dollars: Dollar(0),
-            cents: value,
-        }
-    }
-}
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub(crate) struct Dollar(pub(crate) u64);
-impl Dollar {
-    const ZERO: Self = Self(0);
-}
-impl AddAssign for Dollar {
-    fn add_assign(&mut self, rhs: Self) {
-        self.0.add_assign(rhs.0);
-    }
-}
-impl AddAssign<&Self> for Dollar {
-    fn add_assign(&mut self, rhs: &Self) {
-        self.0.add_assign(rhs.0);
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ParseMoneyError(String);
+impl Display for ParseMoneyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "\"{}\" is not an amount of money like \"12\" or \"12.34\"",
+            self.0
+        )
     }
 }
-impl Add for Dollar {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        let mut result = self;
-        result += rhs;
-        result
-    }
+impl std::error::Error for ParseMoneyError {}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct InsufficientFunds {
+    pub(crate) price: Money,
+    pub(crate) available: Money,
 }
-impl Add<&Self> for Dollar {
-    type Output = Self;
-
-    fn add(self, rhs: &Self) -> Self::Output {
-        let mut result = self;
-        result += rhs;
-        result
+impl Display for InsufficientFunds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "cannot spend ${} with only ${}",
+            self.price, self.available
+        )
     }
 }
-impl SubAssign for Dollar {
-    fn sub_assign(&mut self, rhs: Self) {
-        self.0.sub_assign(rhs.0);
+impl std::error::Error for InsufficientFunds {}
+/// Sent when the player tries to buy something they can't afford.
+#[derive(Event, Debug)]
+pub(crate) struct InsufficientFundsEvent(pub(crate) InsufficientFunds);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum TransactionReason {
+    LotPurchase,
+    Catch,
+    IncomePayout,
+    EntryFee,
+    Reward,
+    Treatment,
+    Enrichment,
+    Release,
+    Refund,
+}
+impl Display for TransactionReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::LotPurchase => "Lot purchase",
+                Self::Catch => "Catch",
+                Self::IncomePayout => "Income payout",
+                Self::EntryFee => "Entry fee",
+                Self::Reward => "Reward",
+                Self::Treatment => "Treatment",
+                Self::Enrichment => "Enrichment",
+                Self::Release => "Release",
+                Self::Refund => "Refund",
+            }
+        )
     }
 }
-impl SubAssign<&Self> for Dollar {
-    fn sub_assign(&mut self, rhs: &Self) {
-        self.0.sub_assign(rhs.0);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum TransactionKind {
+    Credit,
+    Debit,
+}
+/// Sent by [`Wallet`] for every change to the player's [`Money`].
+#[derive(Event, Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct MoneyTransaction {
+    pub(crate) reason: TransactionReason,
+    pub(crate) kind: TransactionKind,
+    pub(crate) amount: Money,
+    /// Seconds since the Unix epoch.
+    pub(crate) timestamp: u64,
+    /// The player's money after the transaction.
+    pub(crate) balance: Money,
+}
+impl MoneyTransaction {
+    fn new(
+        reason: TransactionReason,
+        kind: TransactionKind,
+        amount: &Money,
+        balance: &Money,
+    ) -> Self {
+        Self {
+            reason,
+            kind,
+            amount: amount.clone(),
+            timestamp: unix_seconds(),
+            balance: balance.clone(),
+        }
     }
-}
-impl From<&Cent> for Dollar {
-    fn from(value: &Cent) -> Self {
-        Self(u64::from(value.0 / 100))
+    /// The time of day of the transaction, in UTC.
+    pub(crate) fn time_string(&self) -> String {
+        time_of_day_string(self.timestamp)
     }
 }
-impl Display for Dollar {
+impl Display for MoneyTransaction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        write!(
+            f,
+            "{}  {}  {}${}  = ${}",
+            self.time_string(),
+            self.reason,
+            match self.kind {
+                TransactionKind::Credit => '+',
+                TransactionKind::Debit => '-',
+            },
+            self.amount,
+            self.balance
+        )
     }
 }
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub(crate) struct Cent(pub(crate) u8);
-impl Cent {
-    const ZERO: Self = Self(0);
-}
-impl AddAssign for Cent {
-    fn add_assign(&mut self, rhs: Self) {
-        self.0.add_assign(rhs.0);
+/// The player's [`Money`], which records every change to it as a [`MoneyTransaction`].
+#[derive(SystemParam)]
+pub(crate) struct Wallet<'w> {
+    money: ResMut<'w, Money>,
+    money_transactions: EventWriter<'w, MoneyTransaction>,
+    insufficient_funds_events: EventWriter<'w, InsufficientFundsEvent>,
+}
+impl Wallet<'_> {
+    pub(crate) fn credit(&mut self, amount: &Money, reason: TransactionReason) {
+        *self.money += amount;
+        self.money_transactions.send(MoneyTransaction::new(
+            reason,
+            TransactionKind::Credit,
+            amount,
+            &self.money,
+        ));
+    }
+    /// Spends `price` through [`Money::try_spend`], sending an [`InsufficientFundsEvent`] if it
+    /// can't be afforded.
+    pub(crate) fn try_spend(
+        &mut self,
+        price: &Money,
+        reason: TransactionReason,
+    ) -> Result<(), InsufficientFunds> {
+        if let Err(err) = self.money.try_spend(price) {
+            self.insufficient_funds_events
+                .send(InsufficientFundsEvent(err.clone()));
+            return Err(err);
+        }
+        self.money_transactions.send(MoneyTransaction::new(
+            reason,
+            TransactionKind::Debit,
+            price,
+            &self.money,
+        ));
+        Ok(())
+    }
+}
+/// The most recent [`MoneyTransaction`]s, newest last.
+#[derive(Resource, Default)]
+pub(crate) struct Ledger(pub(crate) VecDeque<MoneyTransaction>);
+impl Ledger {
+    const CAPACITY: usize = 50;
+
+    pub(crate) fn push(&mut self, money_transaction: MoneyTransaction) {
+        if self.0.len() == Self::CAPACITY {
+            self.0.pop_front();
+        }
+        self.0.push_back(money_transaction);
     }
-}
-impl AddAssign<&Self> for Cent {
-    fn add_assign(&mut self, rhs: &Self) {
-        self.0.add_assign(rhs.0);
+    #[allow(clippy::needless_pass_by_value)]
+    fn record(mut ledger: ResMut<Self>, mut money_transactions: EventReader<MoneyTransaction>) {
+        for money_transaction in money_transactions.read() {
+            ledger.push(money_transaction.clone());
+        }
     }
 }
-impl SubAssign for Cent {
-    fn sub_assign(&mut self, rhs: Self) {
-        self.0.sub_assign(rhs.0);
+
+pub(crate) struct MoneyPlugin;
+impl Plugin for MoneyPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Money::default())
+            .insert_resource(Ledger::default())
+            .add_event::<InsufficientFundsEvent>()
+            .add_event::<MoneyTransaction>()
+            .add_systems(Update, Ledger::record);
+    }
+}
+impl FromStr for Money {
+    type Err = ParseMoneyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseMoneyError(s.to_owned());
+        let (dollars, cents) = s.split_once('.').unwrap_or((s, "00"));
+        if cents.len() != 2
+            || !format!("{dollars}{cents}")
+                .bytes()
+                .all(|b| b.is_ascii_digit())
+        {
+            return Err(error());
+        }
+        let dollars: u64 = dollars.parse().map_err(|_| error())?;
+        let cents: u64 = cents.parse().map_err(|_| error())?;
+        Self::from_cents(cents)
+            .checked_add(
+                &Self::from_cents(Self::CENTS_PER_DOLLAR)
+                    .checked_mul(dollars)
+                    .ok_or_else(error)?,
+            )
+            .ok_or_else(error)
     }
 }
-impl SubAssign<&Self> for Cent {
-    fn sub_assign(&mut self, rhs: &Self) {
-        self.0.sub_assign(rhs.0);
+/// Money is stored as its display string, e.g. `"12.34"`, so that save and level files stay
+/// readable.
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
     }
 }
-impl Sub for Cent {
-    type Output = Self;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        let mut result = self;
-        result -= rhs;
-        result
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let string = String::deserialize(deserializer)?;
+        string.parse().map_err(de::Error::custom)
     }
 }
-impl Sub<&Self> for Cent {
-    type Output = Self;
 
-    fn sub(self, rhs: &Self) -> Self::Output {
-        let mut result = self;
-        result -= rhs;
-        result
-    }
-}
-impl Div for Cent {
-    type Output = Self;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    fn div(self, rhs: Self) -> Self::Output {
-        Self(self.0.div(rhs.0))
-    }
-}
-impl RemAssign for Cent {
-    fn rem_assign(&mut self, rhs: Self) {
-        self.0.rem_assign(rhs.0);
+    #[test]
+    fn new() {
+        assert_eq!(Money::new(12, 34), Money::from_cents(1234));
+        assert_eq!(Money::new(0, 99), Money::from_cents(99));
+    }
+    #[test]
+    #[should_panic = "cents must be under 100"]
+    fn new_rejects_whole_dollars_of_cents() {
+        let _ = Money::new(1, 100);
+    }
+    #[test]
+    #[should_panic = "the amount doesn't fit"]
+    fn new_rejects_overflow() {
+        let _ = Money::new(u64::MAX, 0);
+    }
+    #[test]
+    fn parse_display_round_trip() {
+        for string in ["0.00", "0.05", "12.34", "90.00", "184467440737095516.15"] {
+            let money: Money = string.parse().unwrap();
+            assert_eq!(money.to_string(), string);
+        }
+        assert_eq!("12".parse(), Ok(Money::new(12, 0)));
+    }
+    #[test]
+    fn parse_rejects_malformed_amounts() {
+        for string in [
+            "",
+            ".",
+            "12.3",
+            "12.345",
+            "-1.00",
+            "1,000.00",
+            "1.0a",
+            "184467440737095516.16",
+        ] {
+            assert!(string.parse::<Money>().is_err(), "{string}");
+        }
     }
-}
-impl Display for Cent {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+    #[test]
+    fn checked_arithmetic_overflow() {
+        let max = Money::from_cents(u64::MAX);
+        assert_eq!(max.checked_add(&Money::from_cents(1)), None);
+        assert_eq!(Money::ZERO.checked_sub(&Money::from_cents(1)), None);
+        assert_eq!(max.checked_mul(2), None);
+        assert_eq!(Money::new(1, 50).checked_mul(3), Some(Money::new(4, 50)));
+        assert_eq!(max.checked_mul_rate(2.), None);
+        assert_eq!(max.saturating_mul(2), max);
+    }
+    #[test]
+    fn checked_mul_rate_rounds_to_the_nearest_cent() {
+        assert_eq!(
+            Money::new(1, 0).checked_mul_rate(1.006),
+            Some(Money::new(1, 1))
+        );
+        assert_eq!(
+            Money::new(1, 0).checked_mul_rate(1.004),
+            Some(Money::new(1, 0))
+        );
+        assert_eq!(
+            Money::from_cents(3).checked_mul_rate(0.5),
+            Some(Money::from_cents(2))
+        );
+        assert_eq!(Money::new(1, 0).checked_mul_rate(-1.), None);
+        assert_eq!(Money::new(1, 0).checked_mul_rate(f64::NAN), None);
+    }
+    #[test]
+    fn abbreviated() {
+        assert_eq!(Money::new(999, 0).abbreviated(), "999.00");
+        assert_eq!(Money::new(1_000, 0).abbreviated(), "1K");
+        assert_eq!(Money::new(1_150, 0).abbreviated(), "1.15K");
+        assert_eq!(Money::new(999_999, 0).abbreviated(), "999K");
+        assert_eq!(Money::new(1_000_000, 0).abbreviated(), "1M");
     }
 }
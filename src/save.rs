@@ -0,0 +1,333 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use bevy::{
+    app::{App, AppExit, Last, Plugin},
+    asset::AssetServer,
+    ecs::{
+        event::EventReader,
+        system::{Commands, Query, Res, ResMut, Resource, SystemParam},
+    },
+    hierarchy::Parent,
+    log::{error, info},
+    time::{Time, Timer, TimerMode},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    fox::{Fox, FoxId, FoxIds},
+    merge::{
+        enrichment::EnrichmentItem,
+        fox_lot::{FoxLot, FoxLotPrice, FoxSanctuary},
+        holding_pen::{HeldFox, HoldingPen},
+        placement::PlacementTray,
+        release::{ReleasedFox, ReleasedFoxes},
+        FoxStorageInfo,
+    },
+    money::{Ledger, MoneyTransaction},
+    search::Level,
+    FollowMouse, Money,
+};
+
+const SAVE_PATH: &str = "save.ron";
+
+#[derive(Debug)]
+pub(crate) enum SaveError {
+    Io(io::Error),
+    Parse(ron::error::SpannedError),
+    Serialize(ron::Error),
+    UnsupportedVersion {
+        found: u32,
+        expected: u32,
+    },
+    /// A [`FoxSanctuary`] lists a fox that isn't in the world.
+    MissingFox(FoxId),
+}
+impl Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not access the save file: {err}"),
+            Self::Parse(err) => write!(f, "the save file is corrupt: {err}"),
+            Self::Serialize(err) => write!(f, "could not serialize the game: {err}"),
+            Self::UnsupportedVersion { found, expected } => write!(
+                f,
+                "the save file is version {found}, but this build only reads version {expected}"
+            ),
+            Self::MissingFox(fox_id) => {
+                write!(f, "fox {fox_id} is in a sanctuary but doesn't exist")
+            }
+        }
+    }
+}
+impl std::error::Error for SaveError {}
+impl From<io::Error> for SaveError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+impl From<ron::error::SpannedError> for SaveError {
+    fn from(value: ron::error::SpannedError) -> Self {
+        Self::Parse(value)
+    }
+}
+impl From<ron::Error> for SaveError {
+    fn from(value: ron::Error) -> Self {
+        Self::Serialize(value)
+    }
+}
+
+/// Only the version is read first, so that files from other versions are rejected before their
+/// contents are interpreted.
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SaveFile {
+    version: u32,
+    money: Money,
+    fox_lot_price: Money,
+    level: usize,
+    fox_lots: Vec<SavedFoxLot>,
+    /// Saves from before the ledger existed have none.
+    #[serde(default)]
+    ledger: Vec<MoneyTransaction>,
+    #[serde(default)]
+    released_foxes: Vec<ReleasedFox>,
+    #[serde(default)]
+    holding_pen: Vec<HeldFox>,
+    #[serde(default)]
+    placement_tray: Vec<Fox>,
+    /// The [`FoxIds`] to allocate next. Saves without it have their foxes renumbered.
+    #[serde(default)]
+    next_fox_id: Option<u64>,
+}
+#[derive(Serialize, Deserialize)]
+struct SavedFoxLot {
+    x: i32,
+    y: i32,
+    level: u32,
+    foxes: Vec<Fox>,
+    #[serde(default)]
+    items: Vec<EnrichmentItem>,
+}
+impl SaveFile {
+    const VERSION: u32 = 1;
+
+    fn path() -> PathBuf {
+        PathBuf::from(SAVE_PATH)
+    }
+    fn read(path: &Path) -> Result<Option<Self>, SaveError> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let header: SaveHeader = ron::from_str(&contents)?;
+        if header.version != Self::VERSION {
+            return Err(SaveError::UnsupportedVersion {
+                found: header.version,
+                expected: Self::VERSION,
+            });
+        }
+        Ok(Some(ron::from_str(&contents)?))
+    }
+    fn write(&self, path: &Path) -> Result<(), SaveError> {
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        // Write next to the save first so a crash mid-write never truncates the previous save.
+        let temporary_path = path.with_extension("ron.tmp");
+        fs::write(&temporary_path, contents)?;
+        fs::rename(temporary_path, path)?;
+        Ok(())
+    }
+    /// Loads the save file, if there is one.
+    ///
+    /// A file that can't be read is logged and moved aside, so that the next autosave doesn't
+    /// overwrite it, and the game starts fresh.
+    pub(crate) fn load() -> Option<Self> {
+        let path = Self::path();
+        match Self::read(&path) {
+            Ok(save_file) => save_file,
+            Err(err) => {
+                let backup_path = path.with_extension("ron.bak");
+                error!("Could not load {}: {err}", path.display());
+                if fs::rename(&path, &backup_path).is_ok() {
+                    info!("Moved the unreadable save to {}", backup_path.display());
+                }
+                None
+            }
+        }
+    }
+    /// Gives every fox a new id from `fox_ids`.
+    fn renumber_foxes(&mut self, fox_ids: &mut FoxIds) {
+        let foxes = self
+            .fox_lots
+            .iter_mut()
+            .flat_map(|fox_lot| &mut fox_lot.foxes)
+            .chain(
+                self.holding_pen
+                    .iter_mut()
+                    .map(|held_fox| &mut held_fox.fox),
+            )
+            .chain(&mut self.placement_tray);
+        for fox in foxes {
+            fox.reassign_id(fox_ids);
+        }
+    }
+    /// Restores the saved resources and spawns the saved [`FoxLot`] grid.
+    pub(crate) fn spawn(
+        mut self,
+        commands: &mut Commands,
+        asset_server: &Res<AssetServer>,
+        resources: &mut LoadResources,
+    ) {
+        *resources.fox_ids = FoxIds::new(self.next_fox_id.unwrap_or_default());
+        if self.next_fox_id.is_none() {
+            self.renumber_foxes(&mut resources.fox_ids);
+        }
+        *resources.money = self.money;
+        resources.fox_lot_price.0 = self.fox_lot_price;
+        resources.level.0 = self.level;
+        *resources.ledger = Ledger::default();
+        for money_transaction in self.ledger {
+            resources.ledger.push(money_transaction);
+        }
+        resources.released_foxes.0 = self.released_foxes;
+        resources.holding_pen.0 = self.holding_pen;
+        resources.placement_tray.foxes = self.placement_tray;
+        *resources.fox_storage_info = FoxStorageInfo::default();
+        for fox_lot in self.fox_lots {
+            let mut fox_sanctuary = FoxSanctuary::new(fox_lot.level);
+            fox_sanctuary.items = fox_lot.items;
+            resources.fox_storage_info.total_foxes += fox_lot.foxes.len() as u32;
+            resources.fox_storage_info.total_capacity += fox_sanctuary.capacity();
+            FoxLot::spawn_at_grid_pos(
+                commands,
+                asset_server,
+                fox_lot.x,
+                fox_lot.y,
+                fox_sanctuary,
+                fox_lot.foxes,
+            );
+        }
+    }
+}
+#[derive(SystemParam)]
+pub(crate) struct LoadResources<'w> {
+    money: ResMut<'w, Money>,
+    fox_lot_price: ResMut<'w, FoxLotPrice>,
+    level: ResMut<'w, Level>,
+    fox_storage_info: ResMut<'w, FoxStorageInfo>,
+    ledger: ResMut<'w, Ledger>,
+    released_foxes: ResMut<'w, ReleasedFoxes>,
+    holding_pen: ResMut<'w, HoldingPen>,
+    placement_tray: ResMut<'w, PlacementTray>,
+    fox_ids: ResMut<'w, FoxIds>,
+}
+#[derive(SystemParam)]
+struct SaveSnapshot<'w, 's> {
+    money: Res<'w, Money>,
+    fox_lot_price: Res<'w, FoxLotPrice>,
+    level: Res<'w, Level>,
+    ledger: Res<'w, Ledger>,
+    released_foxes: Res<'w, ReleasedFoxes>,
+    holding_pen: Res<'w, HoldingPen>,
+    placement_tray: Res<'w, PlacementTray>,
+    fox_ids: Res<'w, FoxIds>,
+    fox_lots_q: Query<'w, 's, &'static FoxLot>,
+    fox_sanctuaries_q: Query<
+        'w,
+        's,
+        (
+            &'static FoxSanctuary,
+            Option<&'static Parent>,
+            Option<&'static FollowMouse>,
+        ),
+    >,
+    foxes_q: Query<'w, 's, &'static Fox>,
+}
+impl SaveSnapshot<'_, '_> {
+    /// Fails if a sanctuary's fox can't be found, rather than saving the sanctuary without it.
+    fn save_file(&self) -> Result<SaveFile, SaveError> {
+        let foxes: HashMap<FoxId, &Fox> = self.foxes_q.iter().map(|fox| (fox.id(), fox)).collect();
+        let mut fox_lots = vec![];
+        for (fox_sanctuary, parent, follow_mouse) in &self.fox_sanctuaries_q {
+            // A sanctuary being dragged is detached from its lot until it is dropped
+            let fox_lot = parent
+                .map(Parent::get)
+                .or_else(|| follow_mouse.and_then(|follow_mouse| follow_mouse.parent))
+                .and_then(|fox_lot| self.fox_lots_q.get(fox_lot).ok());
+            if let Some(fox_lot) = fox_lot {
+                let (x, y) = fox_lot.grid_pos();
+                let mut saved_foxes = vec![];
+                for fox_id in fox_sanctuary.fox_ids() {
+                    let fox = foxes.get(fox_id).ok_or(SaveError::MissingFox(*fox_id))?;
+                    saved_foxes.push((*fox).clone());
+                }
+                fox_lots.push(SavedFoxLot {
+                    x,
+                    y,
+                    level: fox_sanctuary.level(),
+                    foxes: saved_foxes,
+                    items: fox_sanctuary.items.clone(),
+                });
+            }
+        }
+        Ok(SaveFile {
+            version: SaveFile::VERSION,
+            money: self.money.clone(),
+            fox_lot_price: self.fox_lot_price.0.clone(),
+            level: self.level.0,
+            fox_lots,
+            ledger: self.ledger.0.iter().cloned().collect(),
+            released_foxes: self.released_foxes.0.clone(),
+            holding_pen: self.holding_pen.0.clone(),
+            placement_tray: self.placement_tray.foxes.clone(),
+            next_fox_id: Some(self.fox_ids.next()),
+        })
+    }
+    fn save(&self) {
+        if let Err(err) = self
+            .save_file()
+            .and_then(|save_file| save_file.write(&SaveFile::path()))
+        {
+            error!("Could not save the game: {err}");
+        }
+    }
+}
+#[derive(Resource)]
+struct AutosaveTimer(Timer);
+impl AutosaveTimer {
+    const SECONDS: f32 = 30.;
+}
+impl Default for AutosaveTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(Self::SECONDS, TimerMode::Repeating))
+    }
+}
+
+pub(crate) struct SavePlugin;
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        // Saving in `Last` sees every fox spawned or despawned by the frame's commands
+        app.insert_resource(AutosaveTimer::default())
+            .add_systems(Last, (autosave, save_on_exit));
+    }
+}
+#[allow(clippy::needless_pass_by_value)]
+fn autosave(time: Res<Time>, mut autosave_timer: ResMut<AutosaveTimer>, snapshot: SaveSnapshot) {
+    autosave_timer.0.tick(time.delta());
+    if autosave_timer.0.just_finished() {
+        snapshot.save();
+    }
+}
+#[allow(clippy::needless_pass_by_value)]
+fn save_on_exit(mut app_exit_events: EventReader<AppExit>, snapshot: SaveSnapshot) {
+    if app_exit_events.read().count() > 0 {
+        snapshot.save();
+    }
+}
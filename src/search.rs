@@ -1,5 +1,3 @@
-use std::cmp::Ordering;
-
 use animation::AnimationPlugin;
 use bevy::{
     app::{App, Plugin},
@@ -11,24 +9,24 @@ use bevy::{
     hierarchy::DespawnRecursiveExt,
     state::{
         app::AppExtStates,
-        state::{NextState, OnExit, States},
+        state::{NextState, OnExit, State, States},
     },
     window::SystemCursorIcon,
     winit::cursor::{CursorIcon, CustomCursor},
 };
 use cell::CellPlugin;
+use level::LevelPlugin;
 use ui::{CollectedFoxUI, UIPlugin};
 
-use crate::{
-    app_state::AppState,
-    fox::Fox,
-    merge::{fox_lot::FoxSanctuary, FoxStorageInfo},
-    Money,
-};
+use crate::{app_state::AppState, merge::placement::PlacementTray, Money};
 
 pub mod animation;
 pub mod cell;
+pub mod generator;
+pub mod level;
+pub mod solver;
 pub mod ui;
+pub mod validate;
 
 #[derive(Resource, Default)]
 pub(crate) struct Level(pub(crate) usize);
@@ -73,57 +71,38 @@ impl SearchState {
 pub(crate) struct SearchPlugin;
 impl Plugin for SearchPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((UIPlugin, AnimationPlugin, CellPlugin))
+        app.add_plugins((UIPlugin, AnimationPlugin, CellPlugin, LevelPlugin))
             .insert_resource(Level::default())
             .insert_resource(TotalFoxes::default())
             .insert_resource(FoxesUncovered::default())
             .insert_resource(CatchPrice::default())
             .init_state::<SearchState>()
-            .add_systems(OnExit(AppState::Search), exit);
+            .add_systems(OnExit(AppState::Search), (exit, reset));
     }
 }
+/// Puts the caught foxes in the [`PlacementTray`], for the player to place.
 #[allow(clippy::needless_pass_by_value)]
 pub(crate) fn exit(
     mut commands: Commands,
-    mut fox_storage_info: ResMut<FoxStorageInfo>,
+    mut placement_tray: ResMut<PlacementTray>,
     collected_fox_uis_q: Query<(Entity, &CollectedFoxUI)>,
-    mut fox_sanctuaries_q: Query<(Entity, &mut FoxSanctuary)>,
 ) {
-    let mut foxes: Vec<Fox> = Vec::with_capacity(collected_fox_uis_q.iter().len());
     for (entity, collected_fox_ui) in &collected_fox_uis_q {
         commands.entity(entity).despawn_recursive();
-        foxes.push(collected_fox_ui.0.clone());
+        placement_tray.foxes.push(collected_fox_ui.0.clone());
     }
-    while !foxes.is_empty() {
-        let mut best_sanctuaries = vec![];
-        for fox_sanctuary in &mut fox_sanctuaries_q {
-            if !fox_sanctuary.1.has_room() {
-                continue;
-            }
-            if best_sanctuaries.is_empty() {
-                best_sanctuaries = vec![fox_sanctuary];
-            } else {
-                match fox_sanctuary.1.level().cmp(&best_sanctuaries[0].1.level()) {
-                    Ordering::Greater => best_sanctuaries = vec![fox_sanctuary],
-                    Ordering::Equal => best_sanctuaries.push(fox_sanctuary),
-                    Ordering::Less => {}
-                }
-            }
-        }
-        assert_ne!(best_sanctuaries.len(), 0);
-        let mut current_sanctuary = 0;
-        while !foxes.is_empty() && current_sanctuary < best_sanctuaries.len() {
-            if best_sanctuaries[current_sanctuary].1.has_room() {
-                let entity = best_sanctuaries[current_sanctuary].0;
-                best_sanctuaries[current_sanctuary].1.push_fox(
-                    &mut commands,
-                    entity,
-                    foxes.pop().unwrap(),
-                );
-                fox_storage_info.total_foxes += 1;
-            } else {
-                current_sanctuary += 1;
-            }
-        }
+}
+/// Moves on to the next level if the search was finished, and gets ready for the next search.
+#[allow(clippy::needless_pass_by_value)]
+fn reset(
+    mut level: ResMut<Level>,
+    mut foxes_uncovered: ResMut<FoxesUncovered>,
+    search_state: Res<State<SearchState>>,
+    mut next_search_state: ResMut<NextState<SearchState>>,
+) {
+    if *search_state.get() == SearchState::Finished {
+        level.0 += 1;
     }
+    *foxes_uncovered = FoxesUncovered::default();
+    next_search_state.set(SearchState::Reveal);
 }
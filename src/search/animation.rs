@@ -6,11 +6,13 @@ use bevy::{
     ecs::{
         component::Component,
         entity::Entity,
+        schedule::IntoSystemConfigs,
         system::{Commands, Query, Res, ResMut, Resource},
     },
     hierarchy::DespawnRecursiveExt,
     math::Vec3,
     sprite::Sprite,
+    text::TextColor,
     time::{Time, Timer, TimerMode},
     transform::components::Transform,
     ui::widget::ImageNode,
@@ -20,8 +22,6 @@ use strum::EnumCount;
 use strum_macros::{EnumCount, FromRepr};
 
 trait Fadable {
-    const STRENGTH: f32 = 2.;
-
     fn set_alpha(&mut self, lucency: u32);
     fn get_alpha(lucency: u32) -> f32 {
         (lucency as f32 / Fade::MAX_LUCENCY as f32).powi(2)
@@ -37,6 +37,11 @@ impl Fadable for ImageNode {
         self.color.set_alpha(Self::get_alpha(lucency));
     }
 }
+impl Fadable for TextColor {
+    fn set_alpha(&mut self, lucency: u32) {
+        self.0.set_alpha(Self::get_alpha(lucency));
+    }
+}
 #[derive(Component)]
 pub(crate) struct Fade {
     pub(crate) mode: FadeMode,
@@ -58,12 +63,9 @@ impl Fade {
     #[allow(clippy::needless_pass_by_value)]
     fn system<T: Fadable + Component>(
         mut commands: Commands,
-        time: Res<Time>,
-        mut fade_timer: ResMut<FadeTimer>,
+        fade_timer: Res<FadeTimer>,
         mut fades_q: Query<(Entity, &mut Self, &mut T)>,
     ) {
-        fade_timer.timer.tick(time.delta());
-
         if fade_timer.timer.finished() {
             for (entity, mut fade, mut fade_component) in &mut fades_q {
                 fade.lucency = match &fade.mode {
@@ -135,13 +137,17 @@ struct FadeTimer {
 }
 impl FadeTimer {
     const DURATION_MILLIS: u64 = 30;
+
+    #[allow(clippy::needless_pass_by_value)]
+    fn tick(time: Res<Time>, mut fade_timer: ResMut<Self>) {
+        fade_timer.timer.tick(time.delta());
+    }
 }
 #[derive(Component)]
 pub(crate) struct Jump {
     direction: Direction,
     original_translation: Vec3,
     distance: f32,
-    speed: Speed,
     height: Height,
     time_since_start: f32,
     total_time: f32,
@@ -161,12 +167,18 @@ impl Jump {
             direction,
             original_translation,
             distance,
-            speed,
             height,
             time_since_start: 0.,
             total_time: Self::REFERENCE_TIME / speed as u32 as f32,
         }
     }
+    pub(crate) const fn direction(&self) -> Direction {
+        self.direction
+    }
+    /// How far through the jump this is, from `0.` to `1.`.
+    pub(crate) fn progress(&self) -> f32 {
+        self.time_since_start / self.total_time
+    }
     #[allow(clippy::needless_pass_by_value)]
     fn system(
         mut commands: Commands,
@@ -238,8 +250,13 @@ impl Plugin for AnimationPlugin {
         .add_systems(
             Update,
             (
-                Fade::system::<Sprite>,
-                Fade::system::<ImageNode>,
+                (
+                    Fade::system::<Sprite>,
+                    Fade::system::<ImageNode>,
+                    Fade::system::<TextColor>,
+                )
+                    .after(FadeTimer::tick),
+                FadeTimer::tick,
                 Jump::system,
             ),
         );
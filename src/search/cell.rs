@@ -9,7 +9,7 @@ use bevy::{
         component::Component,
         entity::Entity,
         event::{Event, EventReader, EventWriter},
-        query::With,
+        query::{With, Without},
         schedule::{common_conditions::resource_changed, Condition, IntoSystemConfigs},
         system::{Commands, Query, Res, ResMut, Single, SystemParam},
     },
@@ -22,36 +22,45 @@ use bevy::{
     sprite::{ColorMaterial, MeshMaterial2d, Sprite},
     state::{
         condition::in_state,
-        state::{NextState, OnEnter, State},
+        state::{NextState, OnEnter, OnExit, State},
     },
-    text::TextFont,
+    text::{JustifyText, Text2d, TextColor, TextFont, TextLayout},
     transform::components::Transform,
     ui::widget::Text,
     utils::default,
     window::Window,
 };
 use enum_map::Enum;
-use once_cell::sync::Lazy;
-use std::cell;
-use strum_macros::EnumString;
 
 use crate::{
     app_state::{AppState, AppStateSet, Search},
-    fox::FoxSpecies,
+    fox::species::FoxSpecies,
+    money::{TransactionReason, Wallet},
     search::animation::{Fade, FadeEndMode, FadeMode, Speed},
-    Clickable, Money, Size,
+    Clickable, Size,
 };
 
-use super::{ui::CatchButton, CatchPrice, FoxesUncovered, Level, SearchState, TotalFoxes};
+use super::{
+    level::{CurrentLevel, LevelData, LevelError},
+    ui::CatchButton,
+    CatchPrice, FoxesUncovered, SearchState, TotalFoxes,
+};
 
-#[derive(Component, Clone, Copy, Default)]
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub(crate) struct Cell {
     cell_type: Option<CellType>,
     revealed: bool,
 }
 impl Cell {
     pub(crate) const SIZE: f32 = 100.;
+    pub(crate) const OBSTACLE_CHARS: [char; 2] = ['s', 'l'];
 
+    pub(super) fn is_fox(&self) -> bool {
+        self.cell_type.is_some_and(CellType::is_fox)
+    }
+    pub(super) const fn is_revealed(&self) -> bool {
+        self.revealed
+    }
     pub fn spawn(
         self,
         commands: &mut Commands,
@@ -59,6 +68,7 @@ impl Cell {
         meshes: &mut ResMut<Assets<Mesh>>,
         materials: &mut ResMut<Assets<ColorMaterial>>,
         translation: Vec3,
+        clue: Option<Clue>,
     ) {
         let mut cell = commands.spawn((
             self,
@@ -74,6 +84,10 @@ impl Cell {
             if let Some(cell_type) = &self.cell_type {
                 cell_type.spawn(cell, asset_server, self.revealed);
             }
+            if let Some(clue) = clue {
+                let show_species = matches!(self.cell_type, Some(CellType::PawPrint(..)));
+                CellClue::spawn(cell, &clue, show_species, self.revealed);
+            }
         });
     }
     pub fn spawn_level(
@@ -81,9 +95,9 @@ impl Cell {
         asset_server: &Res<AssetServer>,
         meshes: &mut ResMut<Assets<Mesh>>,
         materials: &mut ResMut<Assets<ColorMaterial>>,
-        level: &Res<Level>,
-    ) -> TotalFoxes {
-        let (cells, total_foxes) = &LEVEL_CELLS[level.0];
+        level_data: &LevelData,
+    ) {
+        let cells = &level_data.cells;
         let height = cells.len();
         let start_y = (height - 1) as f32 / 2.;
         for (y, row) in cells.iter().enumerate() {
@@ -98,43 +112,49 @@ impl Cell {
                         y: (start_y - y as f32) * Self::SIZE,
                         z: 0.,
                     },
+                    Clue::new(cells, x, y),
                 );
             }
         }
-        *total_foxes
     }
 }
-impl From<ObstacleChar> for Cell {
-    fn from(character: ObstacleChar) -> Self {
-        Self {
+impl TryFrom<ObstacleChar> for Cell {
+    type Error = char;
+
+    fn try_from(character: ObstacleChar) -> Result<Self, Self::Error> {
+        Ok(Self {
             cell_type: match character.0 {
-                // Fox Species
-                'V' => Some(CellType::PawPrint(FoxSpecies::Vulpes)),
-                'C' => Some(CellType::PawPrint(FoxSpecies::Corsac)),
+                ' ' => None,
                 // Obstacles
                 's' => Some(CellType::Obstacle(ObstacleType::Stones)),
                 'l' => Some(CellType::Obstacle(ObstacleType::Log)),
-                _ => None,
+                // Fox species' paw prints
+                character => Some(CellType::PawPrint(
+                    FoxSpecies::from_character(character).ok_or(character)?,
+                )),
             },
             ..default()
-        }
+        })
     }
 }
-impl From<FoxChar> for Cell {
-    fn from(character: FoxChar) -> Self {
-        Self {
+impl TryFrom<FoxChar> for Cell {
+    type Error = char;
+
+    fn try_from(character: FoxChar) -> Result<Self, Self::Error> {
+        Ok(Self {
             cell_type: match character.0 {
-                'V' => Some(CellType::Fox(FoxSpecies::Vulpes)),
-                'C' => Some(CellType::Fox(FoxSpecies::Corsac)),
-                _ => None,
+                ' ' => None,
+                character => Some(CellType::Fox(
+                    FoxSpecies::from_character(character).ok_or(character)?,
+                )),
             },
             ..default()
-        }
+        })
     }
 }
 struct ObstacleChar(char);
 struct FoxChar(char);
-#[derive(Component, Debug, EnumString, Clone, Copy)]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
 enum CellType {
     PawPrint(FoxSpecies),
     Obstacle(ObstacleType),
@@ -146,14 +166,13 @@ impl CellType {
             self,
             Search,
             Sprite {
-                image: asset_server.load(format!(
-                    "images/{}.png",
-                    if self.is_fox() {
-                        "Fox".to_owned() // TODO: Remove this if else and create different sprites for different foxes
-                    } else {
-                        format!("{self:?}")
+                image: asset_server.load(match self {
+                    Self::PawPrint(fox_species) => fox_species.data().paw_print.clone(),
+                    Self::Obstacle(obstacle_type) => {
+                        format!("images/Obstacle({obstacle_type:?}).png")
                     }
-                )),
+                    Self::Fox(fox_species) => fox_species.data().sprite.clone(),
+                }),
                 custom_size: Some(Vec2::splat(Cell::SIZE)),
                 ..default()
             },
@@ -175,6 +194,86 @@ impl CellType {
         matches!(self, Self::Fox(..))
     }
 }
+/// The positions of the up to 8 cells surrounding `(x, y)`.
+pub(super) fn neighbours(
+    cells: &[Vec<Cell>],
+    x: usize,
+    y: usize,
+) -> impl Iterator<Item = (usize, usize)> {
+    let height = cells.len();
+    let width = cells[y].len();
+    (y.saturating_sub(1)..(y + 2).min(height))
+        .flat_map(move |neighbour_y| {
+            (x.saturating_sub(1)..(x + 2).min(width))
+                .map(move |neighbour_x| (neighbour_x, neighbour_y))
+        })
+        .filter(move |&position| position != (x, y))
+}
+/// The foxes hidden in the 8 cells surrounding a cell.
+pub(crate) struct Clue {
+    foxes: u32,
+    species: Vec<FoxSpecies>,
+}
+impl Clue {
+    /// Returns [`None`] for fox cells and for cells with no neighbouring foxes.
+    fn new(cells: &[Vec<Cell>], x: usize, y: usize) -> Option<Self> {
+        if cells[y][x].is_fox() {
+            return None;
+        }
+        let mut clue = Self {
+            foxes: 0,
+            species: vec![],
+        };
+        for (neighbour_x, neighbour_y) in neighbours(cells, x, y) {
+            if let Some(CellType::Fox(fox_species)) = cells[neighbour_y][neighbour_x].cell_type {
+                clue.foxes += 1;
+                if !clue.species.contains(&fox_species) {
+                    clue.species.push(fox_species);
+                }
+            }
+        }
+        (clue.foxes > 0).then_some(clue)
+    }
+}
+#[derive(Component)]
+struct CellClue;
+impl CellClue {
+    const FONT_SIZE: f32 = 40.;
+    const SPECIES_FONT_SIZE: f32 = 20.;
+
+    fn spawn(cell: &mut ChildBuilder<'_>, clue: &Clue, show_species: bool, revealed: bool) {
+        let mut cell_clue = cell.spawn((
+            Self,
+            Search,
+            Text2d::new(clue.foxes.to_string()),
+            TextFont::from_font_size(Self::FONT_SIZE),
+            TextLayout::new_with_justify(JustifyText::Center),
+            TextColor::BLACK,
+            Transform::from_translation(1.5 * Vec3::Z),
+            if revealed {
+                Visibility::Visible
+            } else {
+                Visibility::Hidden
+            },
+        ));
+        if show_species {
+            let species = clue
+                .species
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            cell_clue.with_children(|cell_clue| {
+                cell_clue.spawn((
+                    Text2d::new(species),
+                    TextFont::from_font_size(Self::SPECIES_FONT_SIZE),
+                    TextColor::BLACK,
+                    Transform::from_xyz(0., -0.35 * Cell::SIZE, 0.),
+                ));
+            });
+        }
+    }
+}
 #[derive(Component)]
 struct CellCover;
 impl CellCover {
@@ -195,57 +294,66 @@ impl CellCover {
         ));
     }
 }
-#[derive(Debug, Default, Clone, Copy, Enum)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Enum)]
 enum ObstacleType {
     #[default]
     Stones,
     Log,
 }
-pub(crate) static LEVEL_CELLS: Lazy<Vec<(Vec<Vec<Cell>>, TotalFoxes)>> = Lazy::new(|| {
-    vec![cells_from_level_layout(
-        &vec![
-            "C   ", //
-            "l   ", //
-            " s  ", //
-            "    ", //
-        ],
-        &vec![
-            "    ", //
-            " C  ", //
-            "    ", //
-            "    ", //
-        ],
-    )]
-});
-fn cells_from_level_layout(
-    obstacles: &Vec<&str>,
-    foxes: &Vec<&str>,
-) -> (Vec<Vec<Cell>>, TotalFoxes) {
+pub(super) fn cells_from_level_layout(
+    obstacles: &[String],
+    foxes: &[String],
+) -> Result<(Vec<Vec<Cell>>, TotalFoxes), LevelError> {
+    if obstacles.len() != foxes.len() {
+        return Err(LevelError::RowCountMismatch {
+            obstacle_rows: obstacles.len(),
+            fox_rows: foxes.len(),
+        });
+    }
+    let width = obstacles.first().map_or(0, |row| row.chars().count());
+    if width == 0 {
+        return Err(LevelError::Empty);
+    }
     let mut total_foxes = TotalFoxes(0);
-    let mut cells: Vec<Vec<cell::Cell<(Cell, bool)>>> = vec![];
-    for (obstacle_row, fox_row) in obstacles.iter().zip(foxes) {
+    let mut cells = vec![];
+    for (y, (obstacle_row, fox_row)) in obstacles.iter().zip(foxes).enumerate() {
+        for row in [obstacle_row, fox_row] {
+            let found = row.chars().count();
+            if found != width {
+                return Err(LevelError::RowLengthMismatch {
+                    row: y,
+                    expected: width,
+                    found,
+                });
+            }
+        }
         let mut cell_row = vec![];
         for (x, (obstacle_character, fox_character)) in
             obstacle_row.chars().zip(fox_row.chars()).enumerate()
         {
-            let mut cell = if fox_character == ' ' {
-                Cell::from(ObstacleChar(obstacle_character))
-            } else {
+            let unknown_character = |character| LevelError::UnknownCharacter {
+                row: y,
+                column: x,
+                character,
+            };
+            let obstacle_cell =
+                Cell::try_from(ObstacleChar(obstacle_character)).map_err(unknown_character)?;
+            let fox_cell = Cell::try_from(FoxChar(fox_character)).map_err(unknown_character)?;
+            let mut cell = if fox_cell.cell_type.is_some() {
+                if matches!(obstacle_cell.cell_type, Some(CellType::Obstacle(..))) {
+                    return Err(LevelError::FoxOnObstacle { row: y, column: x });
+                }
                 total_foxes.0 += 1;
-                Cell::from(FoxChar(fox_character))
+                fox_cell
+            } else {
+                obstacle_cell
             };
             cell.revealed = x == 0;
-            cell_row.push(cell::Cell::from((cell, true)));
+            cell_row.push(cell);
         }
         cells.push(cell_row);
     }
-    (
-        cells
-            .iter()
-            .map(|row| row.iter().map(|cell_cell| cell_cell.get().0).collect())
-            .collect(),
-        total_foxes,
-    )
+    Ok((cells, total_foxes))
 }
 
 #[derive(Event, Debug)]
@@ -261,10 +369,11 @@ struct CellGroup<'w, 's> {
     covers: Query<'w, 's, (&'static Parent, Entity), With<CellCover>>,
     cells: Query<'w, 's, (&'static mut Cell, &'static Children)>,
     types: Query<'w, 's, (Entity, &'static CellType, &'static mut Visibility)>,
+    clues: Query<'w, 's, &'static mut Visibility, (With<CellClue>, Without<CellType>)>,
 }
 #[derive(SystemParam)]
 struct RevealCellResources<'w> {
-    money: ResMut<'w, Money>,
+    wallet: Wallet<'w>,
     foxes_uncovered: ResMut<'w, FoxesUncovered>,
     search_state: Res<'w, State<SearchState>>,
     catch_price: Res<'w, CatchPrice>,
@@ -272,6 +381,8 @@ struct RevealCellResources<'w> {
 #[derive(SystemParam)]
 struct EndSearchResources<'w> {
     next_search_state: ResMut<'w, NextState<SearchState>>,
+    wallet: Wallet<'w>,
+    current_level: CurrentLevel<'w>,
     asset_server: Res<'w, AssetServer>,
     total_foxes: Res<'w, TotalFoxes>,
     foxes_uncovered: Res<'w, FoxesUncovered>,
@@ -285,6 +396,7 @@ impl Plugin for CellPlugin {
             .add_event::<CellCoverMouseupEvent>()
             .add_event::<FoxCaughtEvent>()
             .add_systems(OnEnter(AppState::Search), startup.after(AppStateSet))
+            .add_systems(OnExit(AppState::Search), despawn_level.after(AppStateSet))
             .add_systems(
                 Update,
                 (
@@ -306,16 +418,28 @@ fn startup(
     asset_server: Res<AssetServer>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
-    level: Res<Level>,
+    current_level: CurrentLevel,
     mut total_foxes: ResMut<TotalFoxes>,
+    mut catch_price: ResMut<CatchPrice>,
 ) {
-    *total_foxes = Cell::spawn_level(
+    let Some(level_data) = current_level.get() else {
+        return;
+    };
+    Cell::spawn_level(
         &mut commands,
         &asset_server,
         &mut meshes,
         &mut materials,
-        &level,
+        level_data,
     );
+    *total_foxes = level_data.total_foxes;
+    catch_price.0 = level_data.catch_price.clone();
+}
+#[allow(clippy::needless_pass_by_value)]
+fn despawn_level(mut commands: Commands, cells_q: Query<Entity, With<Cell>>) {
+    for cell in &cells_q {
+        commands.entity(cell).despawn_recursive();
+    }
 }
 #[allow(clippy::needless_pass_by_value)]
 fn no_mouse_event_cell(
@@ -350,10 +474,21 @@ fn reveal_cell(
     let search_state = resources.search_state.get();
     for ev in cell_cover_event.read() {
         if let Ok((cell_cover_parent, cell_cover)) = cell_group.covers.get(ev.0) {
+            if *search_state == SearchState::Catch
+                && resources
+                    .wallet
+                    .try_spend(&resources.catch_price.0, TransactionReason::Catch)
+                    .is_err()
+            {
+                continue;
+            }
             if let Ok((mut cell, cell_children)) = cell_group.cells.get_mut(cell_cover_parent.get())
             {
                 cell.revealed = true;
                 for cell_child in cell_children {
+                    if let Ok(mut cell_clue_visibility) = cell_group.clues.get_mut(*cell_child) {
+                        *cell_clue_visibility = Visibility::Visible;
+                    }
                     if let Ok((cell_type_entity, cell_type, mut cell_type_visibility)) =
                         cell_group.types.get_mut(*cell_child)
                     {
@@ -373,9 +508,6 @@ fn reveal_cell(
                 }
             }
             commands.entity(cell_cover).despawn_recursive();
-            if *search_state == SearchState::Catch {
-                *resources.money -= resources.catch_price.0.clone();
-            }
         }
     }
 }
@@ -405,6 +537,11 @@ fn end_search(
             *window,
             SearchState::Finished,
         );
+        if let Some(level_data) = resources.current_level.get() {
+            resources
+                .wallet
+                .credit(&level_data.reward, TransactionReason::Reward);
+        }
         let (catch_button, catch_button_children) = catch_button.into_inner();
         for child in catch_button_children {
             commands.entity(*child).despawn_recursive();
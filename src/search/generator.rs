@@ -0,0 +1,148 @@
+use rand::{
+    distr::{weighted::WeightedIndex, Distribution},
+    rngs::StdRng,
+    seq::{IndexedRandom, SliceRandom},
+    Rng, SeedableRng,
+};
+
+use crate::fox::species::FoxSpecies;
+
+use super::{
+    cell::{cells_from_level_layout, Cell},
+    solver::is_solvable,
+    TotalFoxes,
+};
+
+/// What kind of search board [`BoardSettings::generate`] should build.
+pub(crate) struct BoardSettings {
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    pub(crate) foxes: u32,
+    /// Each species and how likely a fox is to be of that species, relative to the others.
+    pub(crate) species: Vec<(FoxSpecies, u32)>,
+    /// The chance of any cell holding an obstacle, from `0.` to `1.`.
+    pub(crate) obstacle_density: f64,
+}
+impl BoardSettings {
+    const MAX_ATTEMPTS: u32 = 1000;
+    const PAW_PRINT_CHANCE: f64 = 0.5;
+
+    /// Builds a random board from `seed` that can be finished from its revealed column without
+    /// guessing.
+    ///
+    /// Returns [`None`] if the settings can't fit the foxes, or no such board turned up.
+    pub(crate) fn generate(&self, seed: u64) -> Option<(Vec<Vec<Cell>>, TotalFoxes)> {
+        // The first column starts revealed, so foxes can only hide in the others
+        if self.width < 2
+            || self.height == 0
+            || self.foxes as usize > (self.width - 1) * self.height
+        {
+            return None;
+        }
+        let species_weights =
+            WeightedIndex::new(self.species.iter().map(|(_, weight)| *weight)).ok()?;
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..Self::MAX_ATTEMPTS).find_map(|_| {
+            let (obstacles, foxes) = self.layout(&mut rng, &species_weights)?;
+            let (cells, total_foxes) = cells_from_level_layout(&obstacles, &foxes).ok()?;
+            is_solvable(&cells, total_foxes).then_some((cells, total_foxes))
+        })
+    }
+    /// Picks obstacle and fox grids in the same format as a level file.
+    fn layout(
+        &self,
+        rng: &mut StdRng,
+        species_weights: &WeightedIndex<u32>,
+    ) -> Option<(Vec<String>, Vec<String>)> {
+        let mut obstacles = vec![vec![' '; self.width]; self.height];
+        let mut foxes = vec![vec![' '; self.width]; self.height];
+        for row in &mut obstacles {
+            for obstacle in row {
+                if rng.random_bool(self.obstacle_density) {
+                    *obstacle = *Cell::OBSTACLE_CHARS.choose(rng)?;
+                }
+            }
+        }
+        let mut fox_positions: Vec<(usize, usize)> = (0..self.height)
+            .flat_map(|y| (1..self.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| obstacles[y][x] == ' ')
+            .collect();
+        if fox_positions.len() < self.foxes as usize {
+            return None;
+        }
+        fox_positions.shuffle(rng);
+        fox_positions.truncate(self.foxes as usize);
+        for &(x, y) in &fox_positions {
+            let fox_species = self.species[species_weights.sample(rng)].0;
+            foxes[y][x] = fox_species.data().character;
+        }
+        // Leave paw prints next to some of the foxes
+        for &(x, y) in &fox_positions {
+            if !rng.random_bool(Self::PAW_PRINT_CHANCE) {
+                continue;
+            }
+            let empty_neighbours: Vec<(usize, usize)> = (y.saturating_sub(1)
+                ..(y + 2).min(self.height))
+                .flat_map(|neighbour_y| {
+                    (x.saturating_sub(1)..(x + 2).min(self.width))
+                        .map(move |neighbour_x| (neighbour_x, neighbour_y))
+                })
+                .filter(|&(neighbour_x, neighbour_y)| {
+                    obstacles[neighbour_y][neighbour_x] == ' '
+                        && foxes[neighbour_y][neighbour_x] == ' '
+                })
+                .collect();
+            if let Some(&(paw_print_x, paw_print_y)) = empty_neighbours.choose(rng) {
+                obstacles[paw_print_y][paw_print_x] = foxes[y][x];
+            }
+        }
+        let to_strings = |grid: Vec<Vec<char>>| grid.into_iter().map(String::from_iter).collect();
+        Some((to_strings(obstacles), to_strings(foxes)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fox::species::load_species;
+
+    use super::*;
+
+    fn board_settings() -> BoardSettings {
+        load_species().unwrap();
+        BoardSettings {
+            width: 6,
+            height: 5,
+            foxes: 4,
+            species: FoxSpecies::all()
+                .map(|fox_species| (fox_species, fox_species.data().rarity_weight))
+                .collect(),
+            obstacle_density: 0.1,
+        }
+    }
+
+    #[test]
+    fn a_seed_always_generates_the_same_board() {
+        let board_settings = board_settings();
+        let (cells, total_foxes) = board_settings.generate(7).unwrap();
+        let (same_cells, same_total_foxes) = board_settings.generate(7).unwrap();
+        assert_eq!(cells, same_cells);
+        assert_eq!(total_foxes.0, same_total_foxes.0);
+    }
+    #[test]
+    fn generated_boards_solve() {
+        let board_settings = board_settings();
+        for seed in 0..50 {
+            let (cells, total_foxes) = board_settings.generate(seed).unwrap();
+            assert_eq!(total_foxes.0, board_settings.foxes, "seed {seed}");
+            assert!(is_solvable(&cells, total_foxes), "seed {seed}");
+        }
+    }
+    #[test]
+    fn too_many_foxes_generate_nothing() {
+        let board_settings = BoardSettings {
+            foxes: 26,
+            ..board_settings()
+        };
+        assert!(board_settings.generate(0).is_none());
+    }
+}
@@ -0,0 +1,280 @@
+use std::{fmt::Display, io};
+
+use bevy::{
+    app::{App, Plugin, Startup, Update},
+    asset::{
+        io::Reader, Asset, AssetApp, AssetLoader, AssetPath, AssetServer, Assets, Handle,
+        LoadContext, LoadedFolder,
+    },
+    ecs::system::{Commands, Res, ResMut, Resource, SystemParam},
+    log::{error, info},
+    reflect::TypePath,
+};
+use serde::Deserialize;
+
+use crate::{fox::species::FoxSpecies, Money};
+
+use super::{
+    cell::{cells_from_level_layout, Cell},
+    generator::BoardSettings,
+    Level, TotalFoxes,
+};
+
+pub(crate) const LEVELS_FOLDER: &str = "levels";
+
+#[derive(Debug)]
+pub(crate) enum LevelError {
+    Io(io::Error),
+    Parse(ron::error::SpannedError),
+    Empty,
+    RowCountMismatch {
+        obstacle_rows: usize,
+        fox_rows: usize,
+    },
+    RowLengthMismatch {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    UnknownCharacter {
+        row: usize,
+        column: usize,
+        character: char,
+    },
+    FoxOnObstacle {
+        row: usize,
+        column: usize,
+    },
+}
+impl Display for LevelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read the level: {err}"),
+            Self::Parse(err) => write!(f, "the level is not valid RON: {err}"),
+            Self::Empty => write!(f, "the level has no cells"),
+            Self::RowCountMismatch {
+                obstacle_rows,
+                fox_rows,
+            } => write!(
+                f,
+                "the obstacle grid has {obstacle_rows} rows but the fox grid has {fox_rows}"
+            ),
+            Self::RowLengthMismatch {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} is {found} characters long, expected {expected}",
+                row + 1
+            ),
+            Self::UnknownCharacter {
+                row,
+                column,
+                character,
+            } => write!(
+                f,
+                "unknown character '{character}' at row {}, column {}",
+                row + 1,
+                column + 1
+            ),
+            Self::FoxOnObstacle { row, column } => write!(
+                f,
+                "a fox is placed on an obstacle at row {}, column {}",
+                row + 1,
+                column + 1
+            ),
+        }
+    }
+}
+impl std::error::Error for LevelError {}
+impl From<io::Error> for LevelError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+impl From<ron::error::SpannedError> for LevelError {
+    fn from(value: ron::error::SpannedError) -> Self {
+        Self::Parse(value)
+    }
+}
+
+/// The contents of a `.level.ron` file, before its grids are turned into [`Cell`]s.
+#[derive(Deserialize)]
+struct LevelFile {
+    name: String,
+    catch_price: Money,
+    reward: Money,
+    #[serde(default = "LevelFile::no_entry_fee")]
+    entry_fee: Money,
+    obstacles: Vec<String>,
+    foxes: Vec<String>,
+}
+impl LevelFile {
+    const fn no_entry_fee() -> Money {
+        Money::ZERO
+    }
+}
+#[derive(Asset, TypePath)]
+pub(crate) struct LevelData {
+    pub(crate) name: String,
+    pub(crate) catch_price: Money,
+    pub(crate) reward: Money,
+    /// Charged when the search starts.
+    pub(crate) entry_fee: Money,
+    pub(crate) cells: Vec<Vec<Cell>>,
+    pub(crate) total_foxes: TotalFoxes,
+}
+impl LevelData {
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Result<Self, LevelError> {
+        let level_file: LevelFile = ron::de::from_bytes(bytes)?;
+        let (cells, total_foxes) =
+            cells_from_level_layout(&level_file.obstacles, &level_file.foxes)?;
+        Ok(Self {
+            name: level_file.name,
+            catch_price: level_file.catch_price,
+            reward: level_file.reward,
+            entry_fee: level_file.entry_fee,
+            cells,
+            total_foxes,
+        })
+    }
+    /// The `generated`th board played after every authored level is finished, with more foxes
+    /// hiding in bigger boards as it goes.
+    fn generate(generated: usize) -> Option<Self> {
+        let board_settings = BoardSettings {
+            width: (5 + generated / 2).min(9),
+            height: (4 + generated / 3).min(7),
+            foxes: (2 + generated / 2).min(8) as u32,
+            species: FoxSpecies::all()
+                .map(|fox_species| (fox_species, fox_species.data().rarity_weight))
+                .collect(),
+            obstacle_density: 0.1,
+        };
+        let (cells, total_foxes) = board_settings.generate(generated as u64)?;
+        Some(Self {
+            name: format!("The Wilds {}", generated + 1),
+            catch_price: Money::new(5, 0).checked_mul_rate(1.1f64.powi(generated as i32))?,
+            reward: Money::new(20, 0).checked_mul_rate(1.25f64.powi(generated as i32))?,
+            entry_fee: Money::new(5, 0).checked_mul(generated as u64)?,
+            cells,
+            total_foxes,
+        })
+    }
+}
+#[derive(Default)]
+struct LevelLoader;
+impl AssetLoader for LevelLoader {
+    type Asset = LevelData;
+    type Settings = ();
+    type Error = LevelError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes).await?;
+        let level_data = LevelData::from_bytes(&bytes)?;
+        info!(
+            "Loaded level \"{}\" from {}: catch ${}, reward ${}",
+            level_data.name,
+            load_context.path().display(),
+            level_data.catch_price,
+            level_data.reward
+        );
+        Ok(level_data)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.ron"]
+    }
+}
+/// Every level in `assets/levels`, in file name order, followed by the generated levels played
+/// so far.
+#[derive(Resource)]
+pub(crate) struct Levels {
+    folder: Handle<LoadedFolder>,
+    levels: Vec<Handle<LevelData>>,
+    /// How many of the levels were authored, once the folder has loaded.
+    authored: Option<usize>,
+}
+impl Levels {
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(
+        mut levels: ResMut<Self>,
+        level: Res<Level>,
+        loaded_folders: Res<Assets<LoadedFolder>>,
+        mut level_data: ResMut<Assets<LevelData>>,
+    ) {
+        let Some(authored) = levels.authored else {
+            levels.load_authored(&loaded_folders);
+            return;
+        };
+        while levels.levels.len() <= level.0 {
+            let generated = levels.levels.len() - authored;
+            let Some(generated_level) = LevelData::generate(generated) else {
+                error!("Could not generate level {}", level.0 + 1);
+                return;
+            };
+            levels.levels.push(level_data.add(generated_level));
+        }
+    }
+    fn load_authored(&mut self, loaded_folders: &Assets<LoadedFolder>) {
+        if let Some(loaded_folder) = loaded_folders.get(&self.folder) {
+            let mut handles = loaded_folder
+                .handles
+                .iter()
+                .filter_map(|handle| handle.clone().try_typed::<LevelData>().ok())
+                .collect::<Vec<_>>();
+            handles.sort_by(|a, b| {
+                a.path()
+                    .map(AssetPath::path)
+                    .cmp(&b.path().map(AssetPath::path))
+            });
+            self.authored = Some(handles.len());
+            self.levels = handles;
+        }
+    }
+}
+/// The [`LevelData`] for the current [`Level`], once it has loaded.
+#[derive(SystemParam)]
+pub(crate) struct CurrentLevel<'w> {
+    level: Res<'w, Level>,
+    levels: Res<'w, Levels>,
+    level_data: Res<'w, Assets<LevelData>>,
+}
+impl CurrentLevel<'_> {
+    pub(crate) fn get(&self) -> Option<&LevelData> {
+        self.levels
+            .levels
+            .get(self.level.0)
+            .and_then(|handle| self.level_data.get(handle))
+    }
+    /// Returns `true` once every authored level has been finished.
+    pub(crate) fn all_complete(&self) -> bool {
+        self.levels
+            .authored
+            .is_some_and(|authored| self.level.0 >= authored)
+    }
+}
+
+pub(crate) struct LevelPlugin;
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<LevelData>()
+            .init_asset_loader::<LevelLoader>()
+            .add_systems(Startup, startup)
+            .add_systems(Update, Levels::system);
+    }
+}
+#[allow(clippy::needless_pass_by_value)]
+fn startup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(Levels {
+        folder: asset_server.load_folder(LEVELS_FOLDER),
+        levels: vec![],
+        authored: None,
+    });
+}
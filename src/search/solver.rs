@@ -0,0 +1,292 @@
+use std::collections::HashSet;
+
+use super::{
+    cell::{neighbours, Cell},
+    TotalFoxes,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Knowledge {
+    Unknown,
+    Safe,
+    Revealed,
+    Fox,
+}
+/// A group of covered cells known to hide exactly `foxes` foxes.
+struct Constraint {
+    cells: Vec<(usize, usize)>,
+    foxes: u32,
+}
+impl Constraint {
+    fn is_subset_of(&self, other: &Self) -> bool {
+        self.cells.iter().all(|cell| other.cells.contains(cell))
+    }
+}
+/// How a board was solved by [`solve`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Solution {
+    /// The fewest safe cells that have to be revealed before every fox is located.
+    pub(crate) min_reveals: u32,
+    /// Covered foxes, each of which needs one catch.
+    pub(crate) catches: u32,
+}
+
+/// Plays `cells` the way a careful player would, starting from the cells that begin revealed and
+/// only ever revealing cells that the clues prove to be safe, and finds the order of reveals that
+/// locates every fox soonest.
+///
+/// Returns [`None`] if the board can't be finished without guessing.
+pub(crate) fn solve(cells: &[Vec<Cell>], total_foxes: TotalFoxes) -> Option<Solution> {
+    let mut knowledge = initial_knowledge(cells);
+    while deduce(cells, &mut knowledge, total_foxes) {}
+    let mut seen = HashSet::from([knowledge.clone()]);
+    let mut reveals_needed = vec![knowledge];
+    // Every state in `reveals_needed` took `min_reveals` reveals to reach
+    for min_reveals in 0.. {
+        if reveals_needed.is_empty() {
+            return None;
+        }
+        if reveals_needed
+            .iter()
+            .any(|knowledge| count(knowledge, Knowledge::Fox) == total_foxes.0)
+        {
+            let catches = cells
+                .iter()
+                .flatten()
+                .filter(|cell| cell.is_fox() && !cell.is_revealed())
+                .count() as u32;
+            return Some(Solution {
+                min_reveals,
+                catches,
+            });
+        }
+        let mut one_more_reveal = vec![];
+        for knowledge in &reveals_needed {
+            for (x, y) in informative_safe_cells(cells, knowledge) {
+                let mut knowledge = knowledge.clone();
+                knowledge[y][x] = Knowledge::Revealed;
+                while deduce(cells, &mut knowledge, total_foxes) {}
+                if seen.insert(knowledge.clone()) {
+                    one_more_reveal.push(knowledge);
+                }
+            }
+        }
+        reveals_needed = one_more_reveal;
+    }
+    unreachable!()
+}
+/// Whether `cells` can be finished without guessing. Cheaper than [`solve`], as knowledge only
+/// ever grows, so revealing safe cells in any order gets as far.
+pub(crate) fn is_solvable(cells: &[Vec<Cell>], total_foxes: TotalFoxes) -> bool {
+    let mut knowledge = initial_knowledge(cells);
+    loop {
+        while deduce(cells, &mut knowledge, total_foxes) {}
+        if count(&knowledge, Knowledge::Fox) == total_foxes.0 {
+            return true;
+        }
+        let Some(safe_cell) = knowledge
+            .iter_mut()
+            .flatten()
+            .find(|k| **k == Knowledge::Safe)
+        else {
+            return false;
+        };
+        *safe_cell = Knowledge::Revealed;
+    }
+}
+/// Safe cells next to a cell that's still unknown. Revealing any other safe cell teaches nothing.
+fn informative_safe_cells(
+    cells: &[Vec<Cell>],
+    knowledge: &[Vec<Knowledge>],
+) -> Vec<(usize, usize)> {
+    let mut informative_safe_cells = vec![];
+    for (y, row) in knowledge.iter().enumerate() {
+        for (x, k) in row.iter().enumerate() {
+            if *k == Knowledge::Safe
+                && neighbours(cells, x, y).any(|(neighbour_x, neighbour_y)| {
+                    knowledge[neighbour_y][neighbour_x] == Knowledge::Unknown
+                })
+            {
+                informative_safe_cells.push((x, y));
+            }
+        }
+    }
+    informative_safe_cells
+}
+fn initial_knowledge(cells: &[Vec<Cell>]) -> Vec<Vec<Knowledge>> {
+    cells
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| match (cell.is_revealed(), cell.is_fox()) {
+                    (true, true) => Knowledge::Fox,
+                    (true, false) => Knowledge::Revealed,
+                    (false, _) => Knowledge::Unknown,
+                })
+                .collect()
+        })
+        .collect()
+}
+fn count(knowledge: &[Vec<Knowledge>], wanted: Knowledge) -> u32 {
+    knowledge.iter().flatten().filter(|k| **k == wanted).count() as u32
+}
+/// Applies every deduction it can find once, and returns `true` if anything was learned.
+fn deduce(cells: &[Vec<Cell>], knowledge: &mut [Vec<Knowledge>], total_foxes: TotalFoxes) -> bool {
+    let mut constraints = vec![];
+    for (y, row) in knowledge.iter().enumerate() {
+        for (x, k) in row.iter().enumerate() {
+            if *k != Knowledge::Revealed {
+                continue;
+            }
+            let mut constraint = Constraint {
+                cells: vec![],
+                foxes: 0,
+            };
+            let mut foxes_found = 0;
+            for (neighbour_x, neighbour_y) in neighbours(cells, x, y) {
+                if cells[neighbour_y][neighbour_x].is_fox() {
+                    constraint.foxes += 1;
+                }
+                match knowledge[neighbour_y][neighbour_x] {
+                    Knowledge::Unknown => constraint.cells.push((neighbour_x, neighbour_y)),
+                    Knowledge::Fox => foxes_found += 1,
+                    Knowledge::Safe | Knowledge::Revealed => {}
+                }
+            }
+            constraint.foxes -= foxes_found;
+            if !constraint.cells.is_empty() {
+                constraints.push(constraint);
+            }
+        }
+    }
+    // The total number of foxes is shown to the player, so it is a clue as well
+    let unknown_cells: Vec<(usize, usize)> = (0..knowledge.len())
+        .flat_map(|y| (0..knowledge[y].len()).map(move |x| (x, y)))
+        .filter(|&(x, y)| knowledge[y][x] == Knowledge::Unknown)
+        .collect();
+    if !unknown_cells.is_empty() {
+        constraints.push(Constraint {
+            cells: unknown_cells,
+            foxes: total_foxes.0 - count(knowledge, Knowledge::Fox),
+        });
+    }
+
+    let mut learned = false;
+    let mut settle = |cells: &[(usize, usize)], foxes: u32| {
+        let settled = if foxes == 0 {
+            Knowledge::Safe
+        } else if foxes as usize == cells.len() {
+            Knowledge::Fox
+        } else {
+            return;
+        };
+        for &(x, y) in cells {
+            if knowledge[y][x] == Knowledge::Unknown {
+                knowledge[y][x] = settled;
+                learned = true;
+            }
+        }
+    };
+    for constraint in &constraints {
+        settle(&constraint.cells, constraint.foxes);
+    }
+    // If one group lies inside another, the cells only in the larger group hide the difference
+    for smaller in &constraints {
+        for larger in &constraints {
+            if smaller.cells.len() < larger.cells.len()
+                && smaller.foxes <= larger.foxes
+                && smaller.is_subset_of(larger)
+            {
+                let difference: Vec<(usize, usize)> = larger
+                    .cells
+                    .iter()
+                    .filter(|cell| !smaller.cells.contains(cell))
+                    .copied()
+                    .collect();
+                settle(&difference, larger.foxes - smaller.foxes);
+            }
+        }
+    }
+    learned
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fox::species::load_species,
+        search::{cell::cells_from_level_layout, level::LevelData},
+    };
+
+    use super::*;
+
+    fn board(obstacles: &[&str], foxes: &[&str]) -> (Vec<Vec<Cell>>, TotalFoxes) {
+        load_species().unwrap();
+        let to_strings = |rows: &[&str]| rows.iter().map(|&row| row.to_owned()).collect::<Vec<_>>();
+        cells_from_level_layout(&to_strings(obstacles), &to_strings(foxes)).unwrap()
+    }
+    /// The knowledge after one round of [`deduce`] from the cells that begin revealed.
+    fn deduce_once(obstacles: &[&str], foxes: &[&str]) -> Vec<Vec<Knowledge>> {
+        let (cells, total_foxes) = board(obstacles, foxes);
+        let mut knowledge = initial_knowledge(&cells);
+        deduce(&cells, &mut knowledge, total_foxes);
+        knowledge
+    }
+
+    #[test]
+    fn neighbours_of_a_clue_without_foxes_are_safe() {
+        let knowledge = deduce_once(&["    "], &["   V"]);
+        assert_eq!(knowledge[0][1], Knowledge::Safe);
+        assert_eq!(knowledge[0][2], Knowledge::Unknown);
+        assert_eq!(knowledge[0][3], Knowledge::Unknown);
+    }
+    #[test]
+    fn covered_neighbours_are_foxes_when_the_clue_counts_all_of_them() {
+        let knowledge = deduce_once(&["  ", "  "], &[" V", " V"]);
+        assert_eq!(knowledge[0][1], Knowledge::Fox);
+        assert_eq!(knowledge[1][1], Knowledge::Fox);
+    }
+    #[test]
+    fn cells_outside_a_contained_group_hide_the_difference() {
+        // Both clues and the fox total put the one fox in the middle column
+        let knowledge = deduce_once(&["   ", "   "], &[" V ", "   "]);
+        assert_eq!(knowledge[0][1], Knowledge::Unknown);
+        assert_eq!(knowledge[1][1], Knowledge::Unknown);
+        assert_eq!(knowledge[0][2], Knowledge::Safe);
+        assert_eq!(knowledge[1][2], Knowledge::Safe);
+    }
+    #[test]
+    fn solve_counts_reveals_and_catches() {
+        let (cells, total_foxes) = board(&["    "], &["   V"]);
+        let solution = solve(&cells, total_foxes).unwrap();
+        assert_eq!(solution.min_reveals, 1);
+        assert_eq!(solution.catches, 1);
+    }
+    #[test]
+    fn boards_that_need_a_guess_are_unsolvable() {
+        // Every clue touches both cells of the last column, but only one of them hides the fox
+        let (cells, total_foxes) = board(&["   ", "   "], &["   ", "  V"]);
+        assert!(solve(&cells, total_foxes).is_none());
+        assert!(!is_solvable(&cells, total_foxes));
+    }
+    #[test]
+    fn authored_levels() {
+        load_species().unwrap();
+        for (bytes, min_reveals, catches) in [
+            (
+                include_bytes!("../../assets/levels/00-meadow.level.ron").as_slice(),
+                0,
+                1,
+            ),
+            (
+                include_bytes!("../../assets/levels/01-riverbank.level.ron").as_slice(),
+                3,
+                2,
+            ),
+        ] {
+            let level_data = LevelData::from_bytes(bytes).unwrap();
+            let solution = solve(&level_data.cells, level_data.total_foxes).unwrap();
+            assert_eq!(solution.min_reveals, min_reveals, "{}", level_data.name);
+            assert_eq!(solution.catches, catches, "{}", level_data.name);
+        }
+    }
+}
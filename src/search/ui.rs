@@ -1,7 +1,7 @@
 use bevy::{
     app::{App, Plugin, Startup, Update},
     asset::AssetServer,
-    color::{palettes::tailwind::ORANGE_300, Color},
+    color::{palettes::tailwind::ORANGE_300, Alpha, Color},
     ecs::{
         component::Component,
         entity::Entity,
@@ -15,7 +15,7 @@ use bevy::{
     math::{Vec2, Vec3Swizzles},
     state::{
         condition::in_state,
-        state::{NextState, State},
+        state::{NextState, OnExit, State},
     },
     text::{TextColor, TextFont},
     transform::components::GlobalTransform,
@@ -30,7 +30,7 @@ use bevy::{
 
 use crate::{
     app_state::{self, AppState, Search},
-    fox::Fox,
+    fox::{Fox, FoxIds},
     search::SearchState,
     ui::{CoinUI, MoneyContainer, RootTrait},
 };
@@ -38,6 +38,7 @@ use crate::{
 use super::{
     animation::{Fade, FadeEndMode, FadeMode, Speed},
     cell::{Cell, FoxCaughtEvent},
+    level::CurrentLevel,
     CatchPrice,
 };
 
@@ -82,11 +83,33 @@ impl TopContainer {
             },
         ))
         .with_children(|top_container| {
+            LevelNameUI::spawn(top_container);
             MoneyContainer::spawn(top_container, asset_server);
         });
     }
 }
 #[derive(Component)]
+struct LevelNameUI;
+impl LevelNameUI {
+    const FONT_SIZE: f32 = 40.;
+
+    fn spawn(top_container: &mut ChildBuilder<'_>) {
+        top_container.spawn((
+            Self,
+            Text::default(),
+            TextFont::from_font_size(Self::FONT_SIZE),
+        ));
+    }
+    #[allow(clippy::needless_pass_by_value)]
+    fn system(current_level: CurrentLevel, mut level_name_uis_q: Query<&mut Text, With<Self>>) {
+        if let Some(level_data) = current_level.get() {
+            for mut level_name_ui in &mut level_name_uis_q {
+                level_name_ui.0.clone_from(&level_data.name);
+            }
+        }
+    }
+}
+#[derive(Component)]
 pub(crate) struct CatchButton;
 impl CatchButton {
     pub(crate) const FONT_SIZE: f32 = 50.;
@@ -103,29 +126,47 @@ impl CatchButton {
             },
         ))
         .with_children(|search_button| {
-            const COIN_SIZE: f32 = CatchButton::FONT_SIZE - 10.;
-            const COIN_MARGIN: f32 = (CatchButton::FONT_SIZE - COIN_SIZE) * 0.5;
-            CoinUI::spawn(
-                search_button,
-                asset_server,
-                Val::Px(COIN_SIZE),
-                Some(Val::Px(COIN_MARGIN)),
-            );
-            search_button.spawn((
-                CatchPriceUI,
-                Text::new("0"),
-                TextFont::from_font_size(Self::FONT_SIZE),
-            ));
-            search_button.spawn((
-                Text::new("Catch"),
-                TextFont::from_font_size(Self::FONT_SIZE),
-                Node {
-                    margin: UiRect::left(Val::Px(Self::FONT_SIZE * 0.5)),
-                    ..default()
-                },
-            ));
+            Self::spawn_children(search_button, asset_server);
         });
     }
+    fn spawn_children(search_button: &mut ChildBuilder<'_>, asset_server: &Res<AssetServer>) {
+        const COIN_SIZE: f32 = CatchButton::FONT_SIZE - 10.;
+        const COIN_MARGIN: f32 = (CatchButton::FONT_SIZE - COIN_SIZE) * 0.5;
+        CoinUI::spawn(
+            search_button,
+            asset_server,
+            Val::Px(COIN_SIZE),
+            Some(Val::Px(COIN_MARGIN)),
+        );
+        search_button.spawn((
+            CatchPriceUI,
+            Text::new("0"),
+            TextFont::from_font_size(Self::FONT_SIZE),
+        ));
+        search_button.spawn((
+            Text::new("Catch"),
+            TextFont::from_font_size(Self::FONT_SIZE),
+            Node {
+                margin: UiRect::left(Val::Px(Self::FONT_SIZE * 0.5)),
+                ..default()
+            },
+        ));
+    }
+    /// Turns the "End Search" button back into the "Catch" button for the next search.
+    #[allow(clippy::needless_pass_by_value)]
+    fn reset(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        catch_button: Single<Entity, With<Self>>,
+    ) {
+        commands
+            .entity(*catch_button)
+            .remove::<(Text, TextFont)>()
+            .despawn_descendants()
+            .with_children(|search_button| {
+                Self::spawn_children(search_button, &asset_server);
+            });
+    }
     #[allow(clippy::needless_pass_by_value)]
     fn system(
         mut commands: Commands,
@@ -194,6 +235,7 @@ impl CollectedFoxUI {
     const SIZE: f32 = Cell::SIZE;
 
     fn spawn(fox_collection_ui: &mut ChildBuilder<'_>, asset_server: &Res<AssetServer>, fox: Fox) {
+        let species_data = fox.species().data();
         fox_collection_ui.spawn((
             Self(fox),
             Fade::new(
@@ -203,8 +245,8 @@ impl CollectedFoxUI {
             ),
             Button,
             ImageNode {
-                image: asset_server.load("images/Fox.png"),
-                color: Color::srgba(1., 1., 1., 0.),
+                image: asset_server.load(&species_data.sprite),
+                color: Color::WHITE.with_alpha(0.),
                 ..default()
             },
             Node {
@@ -291,11 +333,13 @@ pub(super) struct UIPlugin;
 impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, startup.before(app_state::startup))
+            .add_systems(OnExit(AppState::Search), CatchButton::reset)
             .add_systems(
                 Update,
                 (
                     CatchButton::system,
                     CatchPriceUI::system,
+                    LevelNameUI::system,
                     set_search_state_reveal.run_if(
                         input_just_released(KeyCode::Escape).and(in_state(SearchState::Catch)),
                     ),
@@ -332,11 +376,12 @@ fn on_fox_caught(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut fox_caught_event: EventReader<FoxCaughtEvent>,
+    mut fox_ids: ResMut<FoxIds>,
     fox_collection_ui: Single<Entity, With<FoxCollectionUI>>,
 ) {
     for ev in fox_caught_event.read() {
         let fox_species = ev.0;
-        let fox = Fox::new_random(fox_species);
+        let fox = Fox::new_random(fox_species, &mut fox_ids);
         commands
             .entity(*fox_collection_ui)
             .with_children(|fox_collection_ui| {
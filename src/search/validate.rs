@@ -0,0 +1,147 @@
+use std::{fmt::Display, fs, path::Path};
+
+use bevy::app::AppExit;
+
+use crate::ASSETS_FOLDER;
+
+use super::{
+    level::{LevelData, LevelError, LEVELS_FOLDER},
+    solver::{solve, Solution},
+};
+
+enum ValidationError {
+    Level(LevelError),
+    /// Foxes in the starting column have no cover to catch them through, so the search never ends.
+    UncatchableFoxes {
+        catchable: u32,
+        total: u32,
+    },
+    NeedsGuessing,
+}
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Level(err) => write!(f, "{err}"),
+            Self::UncatchableFoxes { catchable, total } => write!(
+                f,
+                "only {catchable} of the {total} foxes can be caught, the rest start revealed"
+            ),
+            Self::NeedsGuessing => write!(f, "the board can't be solved without guessing"),
+        }
+    }
+}
+impl From<LevelError> for ValidationError {
+    fn from(value: LevelError) -> Self {
+        Self::Level(value)
+    }
+}
+
+fn validate_level(path: &Path) -> Result<(LevelData, Solution), ValidationError> {
+    let level_data = LevelData::from_bytes(&fs::read(path).map_err(LevelError::from)?)?;
+    let solution = check_level(&level_data)?;
+    Ok((level_data, solution))
+}
+fn check_level(level_data: &LevelData) -> Result<Solution, ValidationError> {
+    let total = level_data.total_foxes.0;
+    let uncovered = level_data
+        .cells
+        .iter()
+        .flatten()
+        .filter(|cell| cell.is_fox() && cell.is_revealed())
+        .count() as u32;
+    if uncovered > 0 {
+        return Err(ValidationError::UncatchableFoxes {
+            catchable: total - uncovered,
+            total,
+        });
+    }
+    solve(&level_data.cells, level_data.total_foxes).ok_or(ValidationError::NeedsGuessing)
+}
+/// Checks every level in `assets/levels` without opening a window, printing how each one can be
+/// solved or why it can't.
+pub(crate) fn validate_levels() -> AppExit {
+    let folder = Path::new(ASSETS_FOLDER).join(LEVELS_FOLDER);
+    let mut paths = match fs::read_dir(&folder) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.to_string_lossy().ends_with(".level.ron"))
+            .collect::<Vec<_>>(),
+        Err(err) => {
+            eprintln!("Could not read {}: {err}", folder.display());
+            return AppExit::error();
+        }
+    };
+    paths.sort();
+
+    let mut failures = 0;
+    for path in &paths {
+        match validate_level(path) {
+            Ok((level_data, solution)) => println!(
+                "ok    {} ({}): {} foxes, solved with {} reveals at fewest and {} catches",
+                path.display(),
+                level_data.name,
+                level_data.total_foxes.0,
+                solution.min_reveals,
+                solution.catches,
+            ),
+            Err(err) => {
+                failures += 1;
+                println!("error {}: {err}", path.display());
+            }
+        }
+    }
+    println!("{} levels checked, {failures} failed", paths.len());
+    if failures == 0 {
+        AppExit::Success
+    } else {
+        AppExit::error()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fox::species::load_species;
+
+    use super::*;
+
+    fn level(obstacles: &str, foxes: &str) -> LevelData {
+        load_species().unwrap();
+        LevelData::from_bytes(
+            format!(
+                r#"(
+                    name: "Test",
+                    catch_price: "1.00",
+                    reward: "1.00",
+                    obstacles: [{obstacles}],
+                    foxes: [{foxes}],
+                )"#
+            )
+            .as_bytes(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn foxes_in_the_starting_column_are_uncatchable() {
+        assert!(matches!(
+            check_level(&level(r#""    ""#, r#""V  V""#)),
+            Err(ValidationError::UncatchableFoxes {
+                catchable: 1,
+                total: 2
+            })
+        ));
+    }
+    #[test]
+    fn levels_that_need_a_guess_are_rejected() {
+        assert!(matches!(
+            check_level(&level(r#""   ", "   ""#, r#""   ", "  V""#)),
+            Err(ValidationError::NeedsGuessing)
+        ));
+    }
+    #[test]
+    fn solvable_levels_pass() {
+        let solution = check_level(&level(r#""    ""#, r#""   V""#)).ok().unwrap();
+        assert_eq!(solution.min_reveals, 1);
+        assert_eq!(solution.catches, 1);
+    }
+}
@@ -1,23 +1,34 @@
 use bevy::{
     app::{App, Plugin, Update},
     asset::AssetServer,
+    color::{
+        palettes::tailwind::{GREEN_400, RED_400},
+        Alpha, Color,
+    },
     ecs::{
         component::Component,
-        query::With,
-        schedule::{common_conditions::resource_changed, Condition, IntoSystemConfigs},
+        entity::Entity,
+        event::EventReader,
+        query::{Added, With},
+        schedule::IntoSystemConfigs,
         system::{Commands, EntityCommands, Query, Res},
     },
     hierarchy::{BuildChildren, ChildBuild, ChildBuilder},
-    state::condition::state_changed,
-    text::TextFont,
+    text::{TextColor, TextFont},
+    time::{Time, Timer, TimerMode},
     ui::{
         widget::{ImageNode, Text},
-        AlignItems, Node, UiRect, Val,
+        AlignItems, Node, PositionType, UiRect, Val,
     },
     utils::default,
 };
 
-use crate::{app_state::AppState, Money};
+use crate::{
+    merge::IncomePaidEvent,
+    money::InsufficientFundsEvent,
+    search::animation::{Fade, FadeEndMode, FadeMode, Speed},
+    Money,
+};
 
 pub(crate) trait RootTrait {
     fn spawn(commands: &mut Commands, asset_server: &Res<AssetServer>);
@@ -66,21 +77,128 @@ impl MoneyContainer {
                     None,
                 );
                 money_container.spawn((
-                    MoneyUI,
-                    Text::new("0"),
+                    MoneyUI::default(),
+                    Text::new(Money::ZERO.abbreviated()),
                     TextFont::from_font_size(Self::FONT_SIZE),
                 ));
             });
     }
 }
+/// Shows the player's [`Money`], counting towards the new amount whenever it changes.
 #[derive(Component)]
-struct MoneyUI;
+struct MoneyUI {
+    from: Money,
+    to: Money,
+    displayed: Money,
+    count_timer: Timer,
+}
 impl MoneyUI {
+    const COUNT_SECONDS: f32 = 0.5;
+
+    /// Starts at the current [`Money`], so only changes after spawning are counted.
+    #[allow(clippy::needless_pass_by_value)]
+    fn init(money: Res<Money>, mut money_uis_q: Query<(&mut Self, &mut Text), Added<Self>>) {
+        for (mut money_ui, mut money_ui_text) in &mut money_uis_q {
+            money_ui.from = money.clone();
+            money_ui.to = money.clone();
+            money_ui.displayed = money.clone();
+            money_ui_text.0 = money.abbreviated();
+        }
+    }
+    #[allow(clippy::needless_pass_by_value)]
+    fn update(time: Res<Time>, money: Res<Money>, mut money_uis_q: Query<(&mut Self, &mut Text)>) {
+        for (mut money_ui, mut money_ui_text) in &mut money_uis_q {
+            if money_ui.to != *money {
+                money_ui.from = money_ui.displayed.clone();
+                money_ui.to = money.clone();
+                money_ui.count_timer.reset();
+            }
+            if money_ui.displayed == money_ui.to {
+                continue;
+            }
+            money_ui.count_timer.tick(time.delta());
+            money_ui.displayed = money_ui
+                .from
+                .lerp(&money_ui.to, money_ui.count_timer.fraction());
+            money_ui_text.0 = money_ui.displayed.abbreviated();
+        }
+    }
+}
+impl Default for MoneyUI {
+    fn default() -> Self {
+        Self {
+            from: Money::ZERO,
+            to: Money::ZERO,
+            displayed: Money::ZERO,
+            count_timer: Timer::from_seconds(Self::COUNT_SECONDS, TimerMode::Once),
+        }
+    }
+}
+/// A short-lived label, such as "+$x", that fades in and out next to a [`MoneyContainer`].
+#[derive(Component)]
+struct MoneyPulse;
+impl MoneyPulse {
+    const FONT_SIZE: f32 = MoneyContainer::FONT_SIZE * 0.5;
+    const INCOME_COLOR: Color = Color::Srgba(GREEN_400);
+    const INSUFFICIENT_FUNDS_COLOR: Color = Color::Srgba(RED_400);
+
+    fn spawn(money_container: &mut ChildBuilder<'_>, text: String, color: Color) {
+        money_container.spawn((
+            Self,
+            Text::new(text),
+            TextFont::from_font_size(Self::FONT_SIZE),
+            TextColor(color.with_alpha(0.)),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(100.),
+                right: Val::Px(0.),
+                ..default()
+            },
+            Fade::new(
+                FadeMode::Appearing,
+                Speed::Fast,
+                Some(FadeEndMode::BounceOnce(Box::new(Some(FadeEndMode::Delete)))),
+            ),
+        ));
+    }
+    #[allow(clippy::needless_pass_by_value)]
+    fn on_income_paid(
+        mut commands: Commands,
+        mut income_paid_events: EventReader<IncomePaidEvent>,
+        money_containers_q: Query<Entity, With<MoneyContainer>>,
+    ) {
+        for ev in income_paid_events.read() {
+            for money_container in &money_containers_q {
+                commands
+                    .entity(money_container)
+                    .with_children(|money_container| {
+                        Self::spawn(
+                            money_container,
+                            format!("+${}", ev.0.abbreviated()),
+                            Self::INCOME_COLOR,
+                        );
+                    });
+            }
+        }
+    }
     #[allow(clippy::needless_pass_by_value)]
-    fn update(mut money_uis_q: Query<&mut Text, With<Self>>, money: Res<Money>) {
-        let money_string: String = money.to_string();
-        for mut money_ui in &mut money_uis_q {
-            money_ui.0.clone_from(&money_string);
+    fn on_insufficient_funds(
+        mut commands: Commands,
+        mut insufficient_funds_events: EventReader<InsufficientFundsEvent>,
+        money_containers_q: Query<Entity, With<MoneyContainer>>,
+    ) {
+        for ev in insufficient_funds_events.read() {
+            for money_container in &money_containers_q {
+                commands
+                    .entity(money_container)
+                    .with_children(|money_container| {
+                        Self::spawn(
+                            money_container,
+                            format!("Need ${}", ev.0.price.abbreviated()),
+                            Self::INSUFFICIENT_FUNDS_COLOR,
+                        );
+                    });
+            }
         }
     }
 }
@@ -89,7 +207,12 @@ impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            MoneyUI::update.run_if(resource_changed::<Money>.or(state_changed::<AppState>)),
+            (
+                MoneyUI::init,
+                MoneyUI::update.after(MoneyUI::init),
+                MoneyPulse::on_income_paid,
+                MoneyPulse::on_insufficient_funds,
+            ),
         );
     }
 }